@@ -3,12 +3,16 @@
 use crate::{
     styling::{
         border::{Border, BorderRadius, BorderStyle},
+        length::px,
+        preset::Preset,
+        selector::Selector,
         style::Style,
         stylesheet::StyleSheet,
         thickness::Thickness,
+        token::{Elevation, Shape, Typescale},
         StyleExt,
     },
-    theme,
+    theme::{self, theme, ThemeChanged},
     utils::get_display_geometry,
 };
 
@@ -18,6 +22,7 @@ use relm4::{
     factory::{DynamicIndex, FactoryComponent, FactoryVecDeque, FactoryView},
     Component, ComponentParts, ComponentSender, FactorySender, RelmContainerExt,
 };
+use tracing::trace;
 
 #[derive(Debug)]
 struct App {
@@ -47,11 +52,13 @@ impl App {
     }
 }
 
-pub struct AppWidgets {}
+pub struct AppWidgets {
+    row: gtk::ListBoxRow,
+}
 
 impl FactoryComponent for App {
     type Init = Self;
-    type Input = ();
+    type Input = ThemeChanged;
     type Output = ();
     type CommandOutput = ();
     type ParentWidget = gtk::ListBox;
@@ -73,69 +80,118 @@ impl FactoryComponent for App {
         let controller = gtk::EventControllerKey::new();
 
         controller.connect_key_pressed(|_, key, _, _| {
-            println!("{key}");
+            trace!(%key, "key pressed");
 
             glib::Propagation::Proceed
         });
 
+        container.set_css_classes(&["app"]);
         container.set_orientation(gtk::Orientation::Horizontal);
         container.set_spacing(4);
-        returned_widget.set_stylesheet(
-            StyleSheet::new()
-                .default_style(
-                    Style::new()
-                        .background_color(theme().surface_container_highest)
-                        .border_radius(&BorderRadius::All(4))
-                        .font_size(12)
-                        .margin(&Thickness::Custom(2, 0, 2, 0))
-                        .padding(&Thickness::All(4))
-                        .transition("background-color .3s"),
-                )
-                .style_for(
-                    ":focus-within",
-                    Style::new()
-                        .background_color(theme().primary_container)
-                        .color(theme().on_primary_container),
-                )
-                .style_for(
-                    ":first-child",
-                    Style::new().border_radius(&BorderRadius::Custom(8, 8, 4, 4)),
-                )
-                .style_for(
-                    ":last-child",
-                    Style::new().border_radius(&BorderRadius::Custom(4, 4, 8, 8)),
-                ),
-        );
+        returned_widget.set_stylesheet(app_stylesheet());
         returned_widget.add_controller(controller);
 
         let icon = gtk::Image::from_icon_name(self.icon.as_ref().unwrap_or(&String::new()));
         let name = gtk::Label::new(Some(&self.name));
 
+        icon.set_css_classes(&["icon"]);
+        name.set_css_classes(&["name"]);
+
         container.container_add(&icon);
         container.container_add(&name);
 
-        Self::Widgets {}
+        Self::Widgets {
+            row: returned_widget.clone(),
+        }
     }
 
     fn init_model(app: Self::Init, _index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
         app
     }
 
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        ThemeChanged: Self::Input,
+        _sender: FactorySender<Self>,
+    ) {
+        widgets.row.set_stylesheet(app_stylesheet());
+    }
+
     // fn update_view(&self, widgets: &mut Self::Widgets, _sender: FactorySender<Self>) {}
 }
 
+pub const APP_ID: &str = "kz.aiving.app-search";
+pub const TOGGLE_ACTION: &str = "toggle-app-search";
+
+// A launcher that is already running only has its window toggled, it keeps the
+// applications it loaded on startup. The check never registers `APP_ID` itself,
+// the first launcher has to become the primary instance in `RelmApp::run` so
+// its `startup` builds the window.
+#[must_use]
+pub fn toggle_running() -> bool {
+    let Ok(connection) = gio::bus_get_sync(gio::BusType::Session, None::<&gio::Cancellable>) else {
+        return false;
+    };
+
+    let running = connection
+        .call_sync(
+            Some("org.freedesktop.DBus"),
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "NameHasOwner",
+            Some(&(APP_ID,).to_variant()),
+            Some(&glib::VariantTy::new("(b)").unwrap()),
+            gio::DBusCallFlags::NONE,
+            -1,
+            None::<&gio::Cancellable>,
+        )
+        .ok()
+        .and_then(|reply| reply.get::<(bool,)>())
+        .is_some_and(|(running,)| running);
+
+    if !running {
+        return false;
+    }
+
+    let launcher = gio::Application::new(Some(APP_ID), gio::ApplicationFlags::IS_LAUNCHER);
+
+    if launcher.register(None::<&gio::Cancellable>).is_err() {
+        return false;
+    }
+
+    launcher.activate_action(TOGGLE_ACTION, None);
+
+    // Remote actions are sent asynchronously, they have to leave the process
+    // before it exits.
+    let _ = connection.flush_sync(None::<&gio::Cancellable>);
+
+    true
+}
+
 pub struct AppSearch {
     applications: FactoryVecDeque<App>,
+    _monitor: gio::AppInfoMonitor,
 }
 
 #[derive(Debug)]
 pub enum Message {}
 
-pub struct BarWidgets {}
+#[derive(Debug)]
+pub enum AppSearchMessage {
+    Toggle,
+    AppsChanged,
+    ThemeChanged,
+}
+
+pub struct BarWidgets {
+    container: gtk::Box,
+    search: gtk::SearchEntry,
+}
 
 impl Component for AppSearch {
     type Init = ();
-    type Input = ();
+    type Input = AppSearchMessage;
     type Output = ();
     type Root = gtk::ApplicationWindow;
     type Widgets = BarWidgets;
@@ -146,17 +202,37 @@ impl Component for AppSearch {
     }
 
     // Initialize the component.
-    fn init((): Self::Init, window: Self::Root, _: ComponentSender<Self>) -> ComponentParts<Self> {
-        let mut applications = FactoryVecDeque::builder().launch_default().detach();
-        let mut guard = applications.guard();
-
-        for group in App::all() {
-            guard.push_back(group);
+    fn init(
+        (): Self::Init,
+        window: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        // The list is only enumerated again when applications get installed or
+        // removed, so showing the launcher doesn't have to wait for it.
+        let monitor = gio::AppInfoMonitor::get();
+
+        {
+            let sender = sender.clone();
+
+            monitor.connect_changed(move |_| sender.input(AppSearchMessage::AppsChanged));
         }
 
-        guard.drop();
+        let mut model = Self {
+            applications: FactoryVecDeque::builder().launch_default().detach(),
+            _monitor: monitor,
+        };
+
+        model.load_applications();
+
+        let toggle = gio::SimpleAction::new(TOGGLE_ACTION, None);
 
-        let model = Self { applications };
+        {
+            let sender = sender.clone();
+
+            toggle.connect_activate(move |_, _| sender.input(AppSearchMessage::Toggle));
+        }
+
+        relm4::main_application().add_action(&toggle);
 
         let geometry = get_display_geometry();
 
@@ -167,44 +243,27 @@ impl Component for AppSearch {
         window.auto_exclusive_zone_enable();
         window.set_anchor(Edge::Left, true);
 
+        window.add_css_class("app-search");
+
         let container = gtk::Box::default();
 
+        container.set_css_classes(&["launcher"]);
         container.set_orientation(gtk::Orientation::Vertical);
-        container.set_style(
-            Style::new()
-                .margin(&Thickness::Custom(10, 10, 10, 10))
-                .background_color(theme().surface_container)
-                .border(
-                    &Border::default()
-                        .thickness(1)
-                        .style(BorderStyle::Solid)
-                        .color(theme().primary_container),
-                )
-                .border_radius(&BorderRadius::All(8))
-                .padding(&Thickness::All(4))
-                .box_shadow(vec![0, 0, 4], theme().primary_container)
-                .font_family("JetBrainsMono Nerd Font"),
-        );
 
         let search = gtk::SearchEntry::default();
 
-        search.set_style(
-            Style::new()
-                .background_color(theme().surface_container_highest)
-                .border_radius(&BorderRadius::All(12))
-                .padding(&Thickness::All(4))
-                .margin(&Thickness::Bottom(2)),
-        );
+        search.add_css_class("search");
 
         let apps = model.applications.widget();
 
+        apps.set_css_classes(&["apps"]);
         apps.set_selection_mode(gtk::SelectionMode::Single);
 
         let scroller = gtk::ScrolledWindow::default();
 
         scroller.set_child_style(
             scroller.first_child().unwrap(),
-            Style::new().border_radius(&BorderRadius::All(8)),
+            Style::new().shape(Shape::Small),
         );
 
         scroller.set_child(Some(apps));
@@ -215,11 +274,39 @@ impl Component for AppSearch {
 
         window.container_add(&container);
 
-        let widgets = Self::Widgets {};
+        let widgets = Self::Widgets { container, search };
+
+        widgets.apply_style();
+
+        theme::subscribe(sender.input_sender(), |_| AppSearchMessage::ThemeChanged);
 
         ComponentParts { model, widgets }
     }
 
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        _: ComponentSender<Self>,
+        window: &Self::Root,
+    ) {
+        match message {
+            AppSearchMessage::Toggle => {
+                window.set_visible(!window.is_visible());
+
+                if window.is_visible() {
+                    widgets.search.grab_focus();
+                }
+            }
+            AppSearchMessage::AppsChanged => self.load_applications(),
+            AppSearchMessage::ThemeChanged => {
+                widgets.apply_style();
+
+                self.applications.broadcast(ThemeChanged);
+            }
+        }
+    }
+
     fn update_cmd_with_view(
         &mut self,
         _: &mut Self::Widgets,
@@ -229,3 +316,84 @@ impl Component for AppSearch {
     ) {
     }
 }
+
+impl AppSearch {
+    fn load_applications(&mut self) {
+        let mut guard = self.applications.guard();
+
+        guard.clear();
+
+        for app in App::all() {
+            guard.push_back(app);
+        }
+    }
+}
+
+impl BarWidgets {
+    fn apply_style(&self) {
+        let theme = theme();
+
+        self.container.set_style(
+            Preset::Card
+                .style(&theme)
+                .margin(&Thickness::Custom(px(10), px(10), px(10), px(10)))
+                .border(
+                    &Border::default()
+                        .thickness(1)
+                        .style(BorderStyle::Solid)
+                        .color(theme.primary_container),
+                )
+                .shape(Shape::Small)
+                .elevation(Elevation::Level2, theme.primary_container)
+                .font_family("JetBrainsMono Nerd Font"),
+        );
+
+        self.search.set_style(
+            Preset::Chip
+                .style(&theme)
+                .padding(&Thickness::All(px(4)))
+                .margin(&Thickness::Bottom(px(2))),
+        );
+    }
+}
+
+fn app_stylesheet() -> StyleSheet {
+    let theme = theme();
+    let transitions = theme::transitions();
+    let (small, extra_small) = (Shape::Small.radius(), Shape::ExtraSmall.radius());
+
+    StyleSheet::new()
+        .default_style(
+            Style::new()
+                .background_color(theme.surface_container_highest)
+                .shape(Shape::ExtraSmall)
+                .typescale(Typescale::BodySmall)
+                .margin(&Thickness::Custom(px(2), px(0), px(2), px(0)))
+                .padding(&Thickness::All(px(4)))
+                .transition(&[transitions.state.for_property("background-color")]),
+        )
+        .style_for(
+            Selector::new().focus_within(),
+            Style::new()
+                .background_color(theme.primary_container)
+                .color(theme.on_primary_container),
+        )
+        .style_for(
+            Selector::new().first_child(),
+            Style::new().border_radius(&BorderRadius::Custom(
+                small,
+                small,
+                extra_small,
+                extra_small,
+            )),
+        )
+        .style_for(
+            Selector::new().last_child(),
+            Style::new().border_radius(&BorderRadius::Custom(
+                extra_small,
+                extra_small,
+                small,
+                small,
+            )),
+        )
+}
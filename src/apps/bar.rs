@@ -1,48 +1,58 @@
 use crate::{
-    components::{
-        current_track::CurrentTrack, date_time::DateTime, metric::Metrics, volume::Volume,
-    },
-    data::workspace::Workspace,
+    components::{registry, unavailable_reason, BarModule, ModuleContext},
+    config::{BarConfig, BarEdge, BarLayer, ExclusiveZone, ModuleConfig},
     styling::{
-        border::{Border, BorderRadius, BorderStyle},
+        border::{Border, BorderStyle},
+        length::px,
         style::Style,
         thickness::Thickness,
+        token::{Elevation, Shape},
         StyleExt,
     },
-    theme,
-    utils::get_display_geometry,
+    theme::{self, theme},
 };
 
 use gtk::prelude::*;
 use gtk4_layer_shell::{Edge, Layer, LayerShell};
-use hyprland::event_listener::EventListener;
-use relm4::{
-    factory::FactoryVecDeque, Component, ComponentController, ComponentParts, ComponentSender,
-    Controller, RelmContainerExt,
-};
+use relm4::{Component, ComponentParts, ComponentSender, RelmContainerExt, RelmRemoveAllExt};
+use tracing::warn;
+
+struct Section {
+    container: gtk::Box,
+    align: gtk::Align,
+    modules: Vec<(ModuleConfig, Box<dyn BarModule>)>,
+}
+
+pub struct BarInit {
+    pub monitor: gdk::Monitor,
+    pub config: BarConfig,
+}
 
 pub struct Bar {
-    workspaces: FactoryVecDeque<Workspace>,
-    current_track: Controller<CurrentTrack>,
-    volume: Controller<Volume>,
-    metrics: Controller<Metrics>,
-    date_time: Controller<DateTime>,
+    context: ModuleContext,
+    edge: BarEdge,
+    start: Section,
+    center: Section,
+    end: Section,
 }
 
 #[derive(Debug)]
-pub enum Message {
-    ChangedWorkspace,
+pub enum BarMessage {
+    Layout(BarConfig),
+    ThemeChanged,
 }
 
-pub struct BarWidgets {}
+pub struct BarWidgets {
+    bar: gtk::CenterBox,
+}
 
 impl Component for Bar {
-    type Init = ();
-    type Input = ();
+    type Init = BarInit;
+    type Input = BarMessage;
     type Output = ();
     type Root = gtk::ApplicationWindow;
     type Widgets = BarWidgets;
-    type CommandOutput = Message;
+    type CommandOutput = ();
 
     fn init_root() -> Self::Root {
         Self::Root::default()
@@ -50,149 +60,254 @@ impl Component for Bar {
 
     // Initialize the component.
     fn init(
-        (): Self::Init,
+        BarInit { monitor, config }: Self::Init,
         window: Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
-        let mut workspaces = FactoryVecDeque::builder().launch_default().detach();
-        let mut guard = workspaces.guard();
-
-        for group in Workspace::all() {
-            guard.push_back(group);
-        }
-
-        guard.drop();
-
-        let current_track = CurrentTrack::builder().launch(()).detach();
-        let volume = Volume::builder().launch(()).detach();
-        let metrics = Metrics::builder().launch(()).detach();
-        let date_time = DateTime::builder().launch(()).detach();
-
-        let model = Self {
-            workspaces,
-            current_track,
-            volume,
-            metrics,
-            date_time,
+        theme::subscribe(sender.input_sender(), |_| BarMessage::ThemeChanged);
+
+        let orientation = orientation(config.edge);
+
+        let mut model = Self {
+            context: ModuleContext {
+                monitor: monitor.clone(),
+                orientation,
+            },
+            edge: config.edge,
+            start: Section::new("start", gtk::Align::Start, orientation),
+            center: Section::new("center", gtk::Align::Center, orientation),
+            end: Section::new("end", gtk::Align::End, orientation),
         };
 
-        let geometry = get_display_geometry();
-
-        sender.command(|out, shutdown| {
-            shutdown
-                .register(async move {
-                    let mut event_listener = EventListener::new();
+        window.add_css_class("bar-window");
+        window.set_application(Some(&relm4::main_application()));
+        window.init_layer_shell();
+        window.set_monitor(&monitor);
 
-                    {
-                        let out = out.clone();
+        let bar = gtk::CenterBox::default();
 
-                        event_listener.add_workspace_added_handler(move |_| {
-                            out.send(Message::ChangedWorkspace).unwrap();
-                        });
-                    }
+        bar.set_start_widget(Some(&model.start.container));
+        bar.set_center_widget(Some(&model.center.container));
+        bar.set_end_widget(Some(&model.end.container));
 
-                    {
-                        let out = out.clone();
+        window.container_add(&bar);
 
-                        event_listener.add_workspace_destroy_handler(move |_| {
-                            out.send(Message::ChangedWorkspace).unwrap();
-                        });
-                    }
+        let widgets = Self::Widgets { bar };
 
-                    {
-                        let out = out.clone();
+        model.set_layout(&window, &widgets, config);
 
-                        event_listener.add_workspace_change_handler(move |_| {
-                            out.send(Message::ChangedWorkspace).unwrap();
-                        });
-                    }
+        ComponentParts { model, widgets }
+    }
 
-                    if event_listener.start_listener_async().await.is_err() {
-                        println!("warning: failed to start hyprland event listener, workspaces will not work");
-                    }
-                })
-                .drop_on_shutdown()
-        });
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::Input,
+        _: ComponentSender<Self>,
+        window: &Self::Root,
+    ) {
+        match message {
+            BarMessage::Layout(config) => self.set_layout(window, widgets, config),
+            BarMessage::ThemeChanged => widgets.apply_style(self.edge),
+        }
+    }
+}
 
-        let workspaces_box = model.workspaces.widget();
+impl BarWidgets {
+    fn apply_style(&self, edge: BarEdge) {
+        let theme = theme();
 
-        window.set_default_width(geometry.width());
-        window.init_layer_shell();
-        window.set_layer(Layer::Top);
-        window.auto_exclusive_zone_enable();
-        window.set_anchor(Edge::Top, true);
+        // The gap towards the screen edge is wider than the one towards windows.
+        let margin = match edge {
+            BarEdge::Top => Thickness::Custom(px(10), px(10), px(5), px(10)),
+            BarEdge::Bottom => Thickness::Custom(px(5), px(10), px(10), px(10)),
+            BarEdge::Left => Thickness::Custom(px(10), px(5), px(10), px(10)),
+            BarEdge::Right => Thickness::Custom(px(10), px(10), px(10), px(5)),
+        };
 
-        let bar = gtk::CenterBox::default();
+        let style = if edge.is_vertical() {
+            Style::new().min_width(26)
+        } else {
+            Style::new().min_height(26)
+        };
 
-        bar.set_css_classes(&["bar"]);
-        bar.set_orientation(gtk::Orientation::Horizontal);
-        bar.set_style(
-            Style::new()
-                .margin(&Thickness::Custom(10, 10, 5, 10))
-                .background_color(theme().surface_container)
+        self.bar.set_style(
+            style
+                .margin(&margin)
+                .background_color(theme.surface_container)
                 .border(
                     &Border::default()
                         .thickness(1)
                         .style(BorderStyle::Solid)
-                        .color(theme().primary_container),
+                        .color(theme.primary_container),
                 )
-                .border_radius(&BorderRadius::All(32))
-                .padding(&Thickness::All(4))
-                .min_height(26)
-                .box_shadow(vec![0, 0, 4], theme().primary_container)
+                .shape(Shape::Full)
+                .padding(&Thickness::All(px(4)))
+                .elevation(Elevation::Level2, theme.primary_container)
                 .font_family("JetBrainsMono Nerd Font"),
         );
+    }
+}
 
-        workspaces_box.set_css_classes(&["workspaces"]);
-        workspaces_box.set_orientation(gtk::Orientation::Horizontal);
-        workspaces_box.set_spacing(2);
-        workspaces_box.set_style(
-            Style::new()
-                .background_color(theme().surface)
-                .margin(&Thickness::Right(4))
-                .border_radius(&BorderRadius::All(32))
-                .padding(&Thickness::All(4))
-                .min_height(8),
+impl Bar {
+    // Every module with its section and the reason it is unavailable, if it is.
+    pub fn modules(&self) -> impl Iterator<Item = (&'static str, &ModuleConfig, Option<String>)> {
+        [
+            ("start", &self.start),
+            ("center", &self.center),
+            ("end", &self.end),
+        ]
+        .into_iter()
+        .flat_map(|(name, section)| {
+            section
+                .modules
+                .iter()
+                .map(move |(config, module)| (name, config, unavailable_reason(&module.widget())))
+        })
+    }
+
+    fn set_layout(
+        &mut self,
+        window: &gtk::ApplicationWindow,
+        widgets: &BarWidgets,
+        config: BarConfig,
+    ) {
+        let orientation = orientation(config.edge);
+
+        // Modules are built for one orientation, switching it rebuilds them.
+        if orientation != self.context.orientation {
+            self.context.orientation = orientation;
+
+            self.start.set_orientation(orientation);
+            self.center.set_orientation(orientation);
+            self.end.set_orientation(orientation);
+        }
+
+        self.edge = config.edge;
+
+        place(window, &config);
+
+        widgets.bar.set_css_classes(&["bar", config.edge.as_str()]);
+        widgets.bar.set_orientation(orientation);
+        widgets.apply_style(config.edge);
+
+        self.start.set_modules(config.start, &self.context);
+        self.center.set_modules(config.center, &self.context);
+        self.end.set_modules(config.end, &self.context);
+    }
+}
+
+// With `stretch` the bar is anchored to both neighbouring edges as well, so it
+// spans the whole side of the monitor.
+fn place(window: &gtk::ApplicationWindow, config: &BarConfig) {
+    for edge in BarEdge::ALL {
+        window.set_anchor(
+            edge.into(),
+            edge == config.edge || (config.stretch && edge != config.edge.opposite()),
         );
+        window.set_margin(edge.into(), config.margin.get(edge));
+    }
 
-        let right = gtk::Box::default();
+    window.set_layer(config.layer.into());
 
-        right.set_orientation(gtk::Orientation::Horizontal);
-        right.set_spacing(4);
-        right.set_halign(gtk::Align::End);
+    match config.exclusive_zone {
+        ExclusiveZone::Auto => window.auto_exclusive_zone_enable(),
+        ExclusiveZone::Size(size) => window.set_exclusive_zone(size),
+    }
+}
 
-        right.container_add(model.volume.widget());
-        right.container_add(model.metrics.widget());
-        right.container_add(model.date_time.widget());
+const fn orientation(edge: BarEdge) -> gtk::Orientation {
+    if edge.is_vertical() {
+        gtk::Orientation::Vertical
+    } else {
+        gtk::Orientation::Horizontal
+    }
+}
 
-        bar.set_start_widget(Some(workspaces_box));
-        bar.set_center_widget(Some(model.current_track.widget()));
-        bar.set_end_widget(Some(&right));
+impl From<BarEdge> for Edge {
+    fn from(edge: BarEdge) -> Self {
+        match edge {
+            BarEdge::Top => Self::Top,
+            BarEdge::Bottom => Self::Bottom,
+            BarEdge::Left => Self::Left,
+            BarEdge::Right => Self::Right,
+        }
+    }
+}
 
-        window.container_add(&bar);
+impl From<BarLayer> for Layer {
+    fn from(layer: BarLayer) -> Self {
+        match layer {
+            BarLayer::Background => Self::Background,
+            BarLayer::Bottom => Self::Bottom,
+            BarLayer::Top => Self::Top,
+            BarLayer::Overlay => Self::Overlay,
+        }
+    }
+}
 
-        let widgets = Self::Widgets {};
+impl Section {
+    fn new(name: &str, align: gtk::Align, orientation: gtk::Orientation) -> Self {
+        let container = gtk::Box::new(orientation, 4);
 
-        ComponentParts { model, widgets }
+        container.set_css_classes(&["section", name]);
+
+        let mut section = Self {
+            container,
+            align,
+            modules: vec![],
+        };
+
+        section.set_orientation(orientation);
+
+        section
     }
 
-    fn update_cmd_with_view(
-        &mut self,
-        _: &mut Self::Widgets,
-        message: Self::CommandOutput,
-        _: ComponentSender<Self>,
-        _: &Self::Root,
-    ) {
-        let mut guard = self.workspaces.guard();
+    fn set_orientation(&mut self, orientation: gtk::Orientation) {
+        self.modules.clear();
 
-        match message {
-            Message::ChangedWorkspace => {
-                for (index, workspace) in Workspace::all().into_iter().enumerate() {
-                    if let Some(mutable_workspace) = guard.get_mut(index) {
-                        *mutable_workspace = workspace;
+        self.container.set_orientation(orientation);
+
+        if orientation == gtk::Orientation::Vertical {
+            self.container.set_halign(gtk::Align::Fill);
+            self.container.set_valign(self.align);
+        } else {
+            self.container.set_halign(self.align);
+            self.container.set_valign(gtk::Align::Fill);
+        }
+    }
+
+    // Modules whose configuration did not change are moved over as-is, so their
+    // backends (MPRIS, Hyprland listeners, timers) keep running across reloads.
+    fn set_modules(&mut self, modules: Vec<ModuleConfig>, context: &ModuleContext) {
+        let mut previous = std::mem::take(&mut self.modules);
+        let registry = registry();
+
+        self.container.remove_all();
+
+        for config in modules {
+            let module = match previous
+                .iter()
+                .position(|(previous, _)| *previous == config)
+            {
+                Some(index) => previous.remove(index).1,
+                None => match registry.build(&config, context) {
+                    Ok(module) => module,
+                    Err(error) => {
+                        warn!("{error}");
+
+                        continue;
                     }
-                }
-            }
+                },
+            };
+
+            let widget = module.widget();
+
+            widget.add_css_class("module");
+            widget.add_css_class(&config.kind);
+
+            self.container.container_add(&widget);
+            self.modules.push((config, module));
         }
     }
 }
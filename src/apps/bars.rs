@@ -0,0 +1,303 @@
+use crate::{
+    apps::{
+        app_search,
+        bar::{Bar, BarInit, BarMessage},
+    },
+    config::{BarConfig, Config, ConfigError, ThemeConfig, ThemeOverrides},
+    ipc::{self, Call, Request, Response},
+    styling::user::UserStyleSheet,
+    theme,
+    utils::{watch_file, ColorExt},
+};
+use std::{env, ffi::OsString, path::PathBuf};
+
+use gtk::prelude::*;
+use relm4::{Component, ComponentController, ComponentParts, ComponentSender, Controller};
+use tracing::{debug, info, warn};
+
+// Owns one bar window per monitor. Its own root window is never shown, it only
+// keeps the application alive while monitors come and go.
+pub struct Bars {
+    path: PathBuf,
+    overrides: ThemeOverrides,
+    theme: ThemeConfig,
+    config: BarConfig,
+    visible: bool,
+    bars: Vec<(gdk::Monitor, Controller<Bar>)>,
+    user_stylesheet: UserStyleSheet,
+    _monitor: Option<gio::FileMonitor>,
+}
+
+pub struct BarsInit {
+    pub config: Config,
+    pub overrides: ThemeOverrides,
+    pub user_stylesheet: UserStyleSheet,
+}
+
+#[derive(Debug)]
+pub enum BarsMessage {
+    Reload,
+    MonitorsChanged,
+}
+
+impl Component for Bars {
+    type Init = BarsInit;
+    type Input = BarsMessage;
+    type Output = ();
+    type Root = gtk::Window;
+    type Widgets = ();
+    type CommandOutput = Call;
+
+    fn init_root() -> Self::Root {
+        Self::Root::default()
+    }
+
+    fn init(
+        BarsInit {
+            config,
+            overrides,
+            user_stylesheet,
+        }: Self::Init,
+        _: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let monitor = {
+            let sender = sender.clone();
+
+            watch_file(&config.path, move || sender.input(BarsMessage::Reload))
+        };
+
+        sender.command(|out, shutdown| {
+            shutdown
+                .register(async move {
+                    let result = ipc::listen(move |call| {
+                        let _ = out.send(call);
+                    })
+                    .await;
+
+                    if let Err(error) = result {
+                        warn!(%error, "failed to listen for commands");
+                    }
+                })
+                .drop_on_shutdown()
+        });
+
+        if let Some(display) = gdk::Display::default() {
+            display.monitors().connect_items_changed(move |_, _, _, _| {
+                sender.input(BarsMessage::MonitorsChanged);
+            });
+        }
+
+        let mut theme = config.theme;
+
+        overrides.apply(&mut theme);
+
+        let mut model = Self {
+            path: config.path,
+            overrides,
+            theme,
+            config: config.bar,
+            visible: true,
+            bars: vec![],
+            user_stylesheet,
+            _monitor: monitor,
+        };
+
+        model.sync_monitors();
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, message: Self::Input, _: ComponentSender<Self>, _: &Self::Root) {
+        match message {
+            BarsMessage::Reload => match self.reload() {
+                Ok(()) => info!(path = %self.path.display(), "reloaded the configuration"),
+                Err(error) => warn!("{error}, keeping the previous configuration"),
+            },
+            BarsMessage::MonitorsChanged => self.sync_monitors(),
+        }
+    }
+
+    fn update_cmd(&mut self, call: Self::CommandOutput, _: ComponentSender<Self>, _: &Self::Root) {
+        debug!(request = %call.request, "handling a command");
+
+        let response = self.handle(&call.request);
+
+        call.reply(response);
+    }
+}
+
+impl Bars {
+    fn handle(&mut self, request: &Request) -> Response {
+        match request {
+            Request::ToggleAppSearch => self.toggle_app_search()?,
+            Request::Reload => self.reload().map_err(|error| error.to_string())?,
+            Request::Theme(color) => theme::set_source(color.0),
+            Request::Show => self.set_visible(true),
+            Request::Hide => self.set_visible(false),
+            Request::Toggle => self.set_visible(!self.visible),
+            Request::Modules => return Ok(self.modules()),
+        }
+
+        Ok(String::new())
+    }
+
+    fn reload(&mut self) -> Result<(), ConfigError> {
+        let config = Config::load_from(&self.path)?;
+        let mut theme = config.theme;
+
+        self.overrides.apply(&mut theme);
+
+        if theme != self.theme {
+            theme::configure(&theme);
+
+            self.theme = theme;
+        }
+
+        self.user_stylesheet.reload();
+
+        if config.bar != self.config {
+            for (_, bar) in &self.bars {
+                bar.emit(BarMessage::Layout(config.bar.clone()));
+            }
+
+            self.config = config.bar;
+        }
+
+        Ok(())
+    }
+
+    fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+
+        for (_, bar) in &self.bars {
+            bar.widget().set_visible(visible);
+        }
+    }
+
+    // The launcher is its own application, a running one is toggled and
+    // otherwise a new one is started with the same configuration and theme.
+    fn toggle_app_search(&self) -> Result<(), String> {
+        if app_search::toggle_running() {
+            return Ok(());
+        }
+
+        let program = env::current_exe()
+            .map_err(|error| format!("failed to find the barry executable: {error}"))?;
+        let mut args = vec![
+            program.into_os_string(),
+            "--config".into(),
+            self.path.clone().into_os_string(),
+        ];
+
+        args.extend(self.theme_args());
+        args.push("app-search".into());
+
+        let args = args.iter().map(OsString::as_os_str).collect::<Vec<_>>();
+
+        gio::Subprocess::newv(&args, gio::SubprocessFlags::NONE)
+            .map(|_| ())
+            .map_err(|error| format!("failed to start the app search: {error}"))
+    }
+
+    // The command line theme options of the bar. The current seed replaces
+    // the source color and wallpaper, it may have been changed with
+    // `barry msg theme` since the bar started.
+    fn theme_args(&self) -> Vec<OsString> {
+        let mut args = Vec::<OsString>::new();
+
+        if let Some(seed) = theme::settings().seed {
+            args.extend(["--theme".into(), seed.to_hex().into()]);
+        } else if let Some(wallpaper) = &self.overrides.wallpaper {
+            args.extend(["--wallpaper".into(), wallpaper.clone().into_os_string()]);
+        }
+
+        if let Some(mode) = self.overrides.mode {
+            args.extend(["--mode".into(), mode.to_string().into()]);
+        }
+
+        if let Some(variant) = self.overrides.variant {
+            args.extend(["--variant".into(), variant.to_string().into()]);
+        }
+
+        if let Some(contrast) = self.overrides.contrast {
+            args.extend(["--contrast".into(), contrast.to_string().into()]);
+        }
+
+        args
+    }
+
+    // One `<connector> <section> <module> <state>` line per module, the state
+    // is `available` or `unavailable` followed by the reason.
+    fn modules(&self) -> String {
+        self.bars
+            .iter()
+            .flat_map(|(monitor, bar)| {
+                let connector = monitor
+                    .connector()
+                    .map_or_else(|| String::from("unknown"), Into::into);
+
+                bar.model()
+                    .modules()
+                    .map(|(section, config, unavailable)| {
+                        let state = unavailable.map_or_else(
+                            || String::from("available"),
+                            |reason| format!("unavailable: {reason}"),
+                        );
+
+                        format!("{connector} {section} {} {state}\n", config.kind)
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    fn sync_monitors(&mut self) {
+        let monitors = monitors();
+
+        self.bars.retain(|(monitor, bar)| {
+            let connected = monitors.contains(monitor);
+
+            if !connected {
+                info!(monitor = ?monitor.connector(), "monitor disconnected");
+
+                bar.widget().destroy();
+            }
+
+            connected
+        });
+
+        for monitor in monitors {
+            if self.bars.iter().any(|(known, _)| *known == monitor) {
+                continue;
+            }
+
+            info!(monitor = ?monitor.connector(), "monitor connected");
+
+            let bar = Bar::builder()
+                .launch(BarInit {
+                    monitor: monitor.clone(),
+                    config: self.config.clone(),
+                })
+                .detach();
+
+            if self.visible {
+                bar.widget().present();
+            }
+
+            self.bars.push((monitor, bar));
+        }
+    }
+}
+
+fn monitors() -> Vec<gdk::Monitor> {
+    gdk::Display::default()
+        .map(|display| {
+            display
+                .monitors()
+                .iter::<gdk::Monitor>()
+                .filter_map(Result::ok)
+                .collect()
+        })
+        .unwrap_or_default()
+}
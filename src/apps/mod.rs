@@ -1,2 +1,3 @@
 pub mod app_search;
 pub mod bar;
+pub mod bars;
@@ -1,25 +1,40 @@
+use crate::components::{set_unavailable, unavailable, ModuleInit};
 use crate::data::track::Track;
 use crate::mpris::{Player, Players};
-use crate::styling::font::FontWeight;
 use crate::utils::ColorExt;
 use crate::{
-    styling::{border::BorderRadius, style::Style, thickness::Thickness, StyleExt},
-    theme,
+    styling::{
+        background::{Direction, Gradient},
+        length::px,
+        preset::Preset,
+        style::Style,
+        thickness::Thickness,
+        token::{scaled, Shape, Typescale},
+        StyleExt,
+    },
+    theme::{self, theme, SchemeVariant, ThemeChanged},
     widgets::CircularProgress,
 };
 use gtk::prelude::*;
-use material_colors::dynamic_color::Variant;
+use material_colors::color::Argb;
 use material_colors::image::ImageReader;
 use material_colors::scheme::Scheme;
-use material_colors::theme::ThemeBuilder;
 use relm4::{
     Component, ComponentParts, ComponentSender, RelmContainerExt, RelmRemoveAllExt, Sender,
 };
+use serde::Deserialize;
+use tokio::task::JoinSet;
 use tokio_stream::StreamExt;
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct CurrentTrackOptions {}
 
 pub struct CurrentTrack {
     previous_track: Option<Track>,
     track: Option<Track>,
+    source: Option<Argb>,
 }
 
 pub struct PopoverWidgets {
@@ -41,31 +56,38 @@ pub struct CurrentTrackWidgets {
 
 #[derive(Debug)]
 pub enum CurrentTrackMessage {
-    GotTrack(Box<(Option<Scheme>, Option<Track>)>),
+    GotTrack(Box<(Option<Argb>, Option<Track>)>),
     PositionChanged(i64),
     PlayerRemoved,
+    Unavailable(String),
 }
 
-async fn process_player(out: Sender<CurrentTrackMessage>, player: Player<'_>) -> ((), ()) {
-    let metadata = player.get_metadata().await.unwrap();
+fn album_source(track: Option<&Track>) -> Option<Argb> {
+    track
+        .and_then(|track| track.image.as_ref())
+        .and_then(|path| ImageReader::open(path).ok())
+        .map(|image| ImageReader::extract_color(&image))
+}
+
+// Stops once the component is gone and nothing receives the messages anymore.
+async fn process_player(out: Sender<CurrentTrackMessage>, player: Player<'_>) {
+    debug!(player = player.name(), "following a player");
+
     let position = player.get_position().await.unwrap_or_default();
 
-    let track = Track::new(metadata, position);
+    let track = player
+        .get_metadata()
+        .await
+        .and_then(|metadata| Track::new(metadata, position));
 
-    let theme = track
-        .as_ref()
-        .and_then(|track| track.image.as_ref())
-        .and_then(|path| ImageReader::open(path).ok())
-        .map(|image| {
-            ThemeBuilder::with_source(ImageReader::extract_color(&image))
-                .variant(Variant::Content)
-                .build()
-                .schemes
-                .dark
-        });
+    let source = album_source(track.as_ref());
 
-    out.send(CurrentTrackMessage::GotTrack(Box::new((theme, track))))
-        .unwrap();
+    if out
+        .send(CurrentTrackMessage::GotTrack(Box::new((source, track))))
+        .is_err()
+    {
+        return;
+    }
 
     tokio::join!(
         async {
@@ -74,22 +96,26 @@ async fn process_player(out: Sender<CurrentTrackMessage>, player: Player<'_>) ->
             while let Some(event) = stream.next().await {
                 let position = player.get_position().await.unwrap_or_default();
 
-                let track = Track::new(event.get().await.unwrap(), position);
-
-                let theme = track
-                    .as_ref()
-                    .and_then(|track| track.image.as_ref())
-                    .and_then(|path| ImageReader::open(path).ok())
-                    .map(|image| {
-                        ThemeBuilder::with_source(ImageReader::extract_color(&image))
-                            .variant(Variant::Content)
-                            .build()
-                            .schemes
-                            .dark
-                    });
-
-                out.send(CurrentTrackMessage::GotTrack(Box::new((theme, track))))
-                    .unwrap();
+                let track = event
+                    .get()
+                    .await
+                    .ok()
+                    .and_then(|metadata| Track::new(metadata, position));
+
+                debug!(
+                    player = player.name(),
+                    title = track.as_ref().map(|track| track.title.as_str()),
+                    "track changed"
+                );
+
+                let source = album_source(track.as_ref());
+
+                if out
+                    .send(CurrentTrackMessage::GotTrack(Box::new((source, track))))
+                    .is_err()
+                {
+                    break;
+                }
             }
         },
         async {
@@ -97,12 +123,27 @@ async fn process_player(out: Sender<CurrentTrackMessage>, player: Player<'_>) ->
                 tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
 
                 if let Some(position) = player.get_position().await {
-                    out.send(CurrentTrackMessage::PositionChanged(position))
-                        .unwrap();
+                    if out
+                        .send(CurrentTrackMessage::PositionChanged(position))
+                        .is_err()
+                    {
+                        break;
+                    }
                 }
             }
         }
-    )
+    );
+}
+
+// The album art in the bar, the popover shows it at a larger size.
+fn art_style() -> Style {
+    Style::new()
+        .background_size("cover")
+        .background_repeat("no-repeat")
+        .background_position("center")
+        .shape(Shape::Medium)
+        .margin(&Thickness::All(scaled(2)))
+        .min_size(scaled(20))
 }
 
 impl Component for CurrentTrack {
@@ -110,47 +151,67 @@ impl Component for CurrentTrack {
     type Widgets = CurrentTrackWidgets;
 
     type CommandOutput = CurrentTrackMessage;
-    type Input = ();
+    type Input = ThemeChanged;
     type Output = ();
-    type Init = ();
+    type Init = ModuleInit<CurrentTrackOptions>;
 
     fn init_root() -> Self::Root {
         Self::Root::default()
     }
 
     fn init(
-        (): Self::Init,
+        ModuleInit { context, .. }: Self::Init,
         root: Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
         let model = Self {
             previous_track: None,
             track: None,
+            source: None,
         };
 
         sender.command(|out, shutdown| {
             shutdown
                 .register(async move {
-                    let players = Players::new().await;
+                    let players = match Players::new().await {
+                        Ok(players) => players,
+                        Err(error) => {
+                            let reason = format!("failed to connect to the session bus: {error}");
+
+                            warn!("{reason}");
+
+                            let _ = out.send(CurrentTrackMessage::Unavailable(reason));
+
+                            return;
+                        }
+                    };
                     let stream = players.owner_changed_steam().await;
 
-                    let mut future = Player::find_active()
-                        .await
-                        .map(|player| tokio::spawn(process_player(out.clone(), player)));
+                    // Dropped with the command on shutdown, which aborts the
+                    // player task too.
+                    let mut following = JoinSet::new();
+
+                    if let Some(player) = Player::find_active().await {
+                        following.spawn(process_player(out.clone(), player));
+                    }
 
                     if let Some(mut stream) = stream {
                         while let Some(owner) = stream.next().await {
                             if let Ok(owner) = owner.args() {
                                 if owner.new_owner.is_none() {
-                                    if let Some(handle) = future.take() {
-                                        handle.abort();
+                                    if !following.is_empty() {
+                                        debug!(player = %owner.name, "player went away");
+
+                                        following.shutdown().await;
 
-                                        out.send(CurrentTrackMessage::PlayerRemoved).unwrap();
+                                        if out.send(CurrentTrackMessage::PlayerRemoved).is_err() {
+                                            break;
+                                        }
+                                    }
+                                } else if following.is_empty() {
+                                    if let Some(player) = Player::find_active().await {
+                                        following.spawn(process_player(out.clone(), player));
                                     }
-                                } else if future.is_none() {
-                                    future = Player::find_active().await.map(|player| {
-                                        tokio::spawn(process_player(out.clone(), player))
-                                    });
                                 }
                             }
                         }
@@ -169,38 +230,23 @@ impl Component for CurrentTrack {
         let title = gtk::Label::default();
         let progress = gtk::ProgressBar::default();
 
-        container.set_style(
-            Style::new()
-                .background_color(theme().surface_bright)
-                .border_radius(&BorderRadius::All(12))
-                .padding(&Thickness::All(4)),
-        );
+        popover.add_css_class("track-popover");
+        image_container.set_css_classes(&["art"]);
+        title.set_css_classes(&["title"]);
+        artist.set_css_classes(&["artist"]);
+        progress.add_css_class("progress");
 
-        image_container.set_style(
-            Style::new()
-                .border_radius(&BorderRadius::All(12))
-                .min_size(48),
-        );
+        image_container.set_style(Style::new().shape(Shape::Medium).min_size(scaled(48)));
 
         title.set_halign(gtk::Align::Start);
         artist.set_halign(gtk::Align::Start);
 
-        progress.set_style(Style::new().margin(&Thickness::Custom(0, 4, 4, 4)));
-        progress.set_child_style(
-            progress.first_child().unwrap(),
-            Style::new()
-                .background_color(theme().on_primary_fixed_variant)
-                .border_radius(&BorderRadius::All(3))
-                .min_height(6),
-        );
-
-        progress.set_child_style(
-            progress.first_child().unwrap().first_child().unwrap(),
-            Style::new()
-                .background_color(theme().primary_fixed)
-                .border_radius(&BorderRadius::All(3))
-                .min_height(6),
-        );
+        progress.set_style(Style::new().margin(&Thickness::Custom(
+            px(0),
+            scaled(4),
+            scaled(4),
+            scaled(4),
+        )));
 
         data_container.container_add(&title);
         data_container.container_add(&artist);
@@ -213,6 +259,7 @@ impl Component for CurrentTrack {
 
         popover.set_child(Some(&container));
 
+        root.set_css_classes(&["current-track"]);
         root.set_cursor_from_name(Some("pointer"));
         root.set_popover(Some(&popover));
 
@@ -224,262 +271,269 @@ impl Component for CurrentTrack {
             title,
         };
 
-        let container = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        let container = gtk::Box::new(context.orientation, 4);
 
+        container.set_css_classes(&["track"]);
         container.set_visible(false);
-        container.set_style(
-            Style::new()
-                .background_color(theme().surface_container_highest)
-                .transition("background-color 1s")
-                .border_radius(&BorderRadius::All(12)),
-        );
 
         let progress = CircularProgress::new();
 
+        progress.add_css_class("progress");
         progress.set_value(0.0);
         progress.set_start_at(75.0);
         progress.set_thickness(2.0);
         progress.set_clockwise(true);
-        progress.set_background_color(theme().on_primary.as_rgba());
         progress.set_width_request(24);
         progress.set_height_request(24);
-        progress.set_style(
-            Style::new()
-                .color(theme().primary)
-                .border_radius(&BorderRadius::All(12))
-                .min_size(24),
-        );
 
         let image = gtk::Box::default();
 
+        image.set_css_classes(&["art"]);
         image.set_visible(false);
-        image.set_style(
-            Style::new()
-                // .background_image(path)
-                .background_size("cover")
-                .background_repeat("no-repeat")
-                .background_position("center")
-                .border_radius(&BorderRadius::All(12))
-                .margin(&Thickness::All(2))
-                .min_size(20),
-        );
+        image.set_style(art_style());
 
         progress.set_child(image);
 
         let artist = gtk::Label::new(None);
 
-        artist.set_style(Style::new().color(theme().secondary).transition("color 1s"));
+        artist.set_css_classes(&["artist"]);
 
         let delimiter = gtk::Label::new(Some("-"));
 
-        delimiter.set_style(
-            Style::new()
-                .color(theme().on_surface_variant)
-                .transition("color 1s"),
-        );
+        delimiter.set_css_classes(&["delimiter"]);
 
         let title = gtk::Label::new(None);
 
-        title.set_style(
-            Style::new()
-                .margin(&Thickness::Right(8))
-                .color(theme().primary)
-                .transition("color 1s")
-                .font_weight(&FontWeight::Bold),
-        );
+        title.set_css_classes(&["title"]);
 
         container.container_add(&progress);
-        container.container_add(&artist);
-        container.container_add(&delimiter);
-        container.container_add(&title);
+
+        // There is no room for the labels on a vertical bar, the popover still
+        // shows them.
+        if context.orientation == gtk::Orientation::Horizontal {
+            container.container_add(&artist);
+            container.container_add(&delimiter);
+            container.container_add(&title);
+        }
 
         root.set_child(Some(&container));
 
-        ComponentParts {
-            model,
-            widgets: Self::Widgets {
-                container,
-                progress,
-                artist,
-                delimiter,
-                title,
-                popover,
-            },
-        }
+        let widgets = Self::Widgets {
+            container,
+            progress,
+            artist,
+            delimiter,
+            title,
+            popover,
+        };
+
+        widgets.apply_style(&theme());
+
+        theme::subscribe(sender.input_sender(), |_| ThemeChanged);
+
+        ComponentParts { model, widgets }
     }
 
-    fn update_cmd_with_view(
+    fn update_with_view(
         &mut self,
         widgets: &mut Self::Widgets,
-        message: Self::CommandOutput,
+        ThemeChanged: Self::Input,
         _: ComponentSender<Self>,
         _: &Self::Root,
     ) {
-        let Self::Widgets {
-            container,
-            progress,
-            artist,
-            delimiter,
-            title,
-            popover,
-        } = widgets;
+        widgets.apply_style(&self.scheme());
+    }
 
+    fn update_cmd_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        message: Self::CommandOutput,
+        _: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
         match message {
             CurrentTrackMessage::GotTrack(track) => {
-                if let (theme_, Some(track)) = track.as_ref() {
-                    if container.is_visible()
+                let (source, track) = *track;
+
+                if let Some(track) = track.as_ref() {
+                    if widgets.container.is_visible()
                         && (track.id.ends_with("NoTrack") || track.duration == 0)
                     {
-                        container.set_visible(false);
+                        widgets.container.set_visible(false);
                     } else if !track.id.ends_with("NoTrack") && track.duration != 0
                         || self.track != self.previous_track && !track.title.is_empty()
                     {
-                        #[allow(clippy::or_fun_call)]
-                        let theme = theme_.as_ref().unwrap_or(theme());
-
-                        popover.container.set_style(
-                            Style::new()
-                                .background_color(theme.surface_bright)
-                                .border_radius(&BorderRadius::All(12))
-                                .padding(&Thickness::All(4)),
-                        );
-
-                        container.set_style(
-                            Style::new()
-                                .background_color(theme.surface_container_highest)
-                                .transition("background-color 1s")
-                                .border_radius(&BorderRadius::All(12)),
-                        );
+                        self.source = source;
+
+                        widgets.apply_style(&self.scheme());
 
                         let value = (track.position as f64 / track.duration as f64).clamp(0.0, 1.0);
                         let value = if value.is_nan() { 0.0 } else { value };
 
-                        popover.progress.set_fraction(value);
-                        popover.progress.set_child_style(
-                            popover.progress.first_child().unwrap(),
-                            Style::new()
-                                .background_color(theme.on_secondary_fixed_variant)
-                                .border_radius(&BorderRadius::All(3))
-                                .min_height(6),
-                        );
-
-                        popover.progress.set_child_style(
-                            popover
-                                .progress
-                                .first_child()
-                                .unwrap()
-                                .first_child()
-                                .unwrap(),
-                            Style::new()
-                                .background_color(theme.secondary_fixed)
-                                .border_radius(&BorderRadius::All(3))
-                                .min_height(6),
-                        );
-
-                        progress.set_value(value * 100.0);
-                        progress.set_background_color(theme.on_primary.as_rgba());
-                        progress.set_style(
-                            Style::new()
-                                .color(theme.primary)
-                                .border_radius(&BorderRadius::All(12))
-                                .min_size(24),
-                        );
-
-                        let image = progress.child().unwrap();
+                        widgets.popover.progress.set_fraction(value);
+                        widgets.progress.set_value(value * 100.0);
+
+                        let image = widgets.progress.child().unwrap();
+                        let art = art_style();
 
                         if let Some(path) = track.image.as_ref() {
                             image.set_visible(true);
-                            image.set_style(
-                                Style::new()
-                                    .background_image(path)
-                                    .background_size("cover")
-                                    .background_repeat("no-repeat")
-                                    .background_position("center")
-                                    .border_radius(&BorderRadius::All(12))
-                                    .margin(&Thickness::All(2))
-                                    .min_size(20),
-                            );
+                            image
+                                .set_style(art.clone().merge(&Style::new().background_image(path)));
 
                             let picture = gtk::Picture::for_filename(path);
 
+                            picture.set_css_classes(&["art"]);
                             picture.set_content_fit(gtk::ContentFit::Cover);
-                            picture.set_style(
-                                Style::new()
-                                    .border_radius(&BorderRadius::All(12))
-                                    .margin(&Thickness::All(2))
-                                    .min_size(48),
-                            );
-
-                            popover.image_container.remove_all();
-                            popover.image_container.container_add(&picture);
+                            picture.set_style(art.merge(&Style::new().min_size(scaled(48))));
+
+                            widgets.popover.image_container.remove_all();
+                            widgets.popover.image_container.container_add(&picture);
                         } else {
                             image.set_visible(false);
-                            image.set_style(
-                                Style::new()
-                                    .background_size("cover")
-                                    .background_repeat("no-repeat")
-                                    .background_position("center")
-                                    .border_radius(&BorderRadius::All(12))
-                                    .margin(&Thickness::All(2))
-                                    .min_size(20),
-                            );
-
-                            popover.image_container.remove_all();
+                            image.set_style(art);
+
+                            widgets.popover.image_container.remove_all();
                         }
 
-                        popover.artist.set_label(&track.artist);
-                        popover
-                            .artist
-                            .set_style(Style::new().color(theme.secondary).transition("color 1s"));
-                        popover.title.set_label(&track.title);
-                        popover.title.set_style(
-                            Style::new()
-                                .color(theme.primary)
-                                .transition("color 1s")
-                                .font_weight(&FontWeight::Bold),
-                        );
-
-                        artist.set_label(&track.artist);
-                        artist
-                            .set_style(Style::new().color(theme.secondary).transition("color 1s"));
-                        delimiter.set_style(
-                            Style::new()
-                                .color(theme.on_surface_variant)
-                                .transition("color 1s"),
-                        );
-                        title.set_label(&track.title);
-                        title.set_style(
-                            Style::new()
-                                .margin(&Thickness::Right(8))
-                                .color(theme.primary)
-                                .transition("color 1s")
-                                .font_weight(&FontWeight::Bold),
-                        );
-
-                        container.set_visible(true);
+                        widgets.popover.artist.set_label(&track.artist);
+                        widgets.popover.title.set_label(&track.title);
+
+                        widgets.artist.set_label(&track.artist);
+                        widgets.title.set_label(&track.title);
+
+                        widgets.container.set_visible(true);
                     }
-                } else if container.is_visible() {
-                    container.set_visible(false);
+                } else if widgets.container.is_visible() {
+                    widgets.container.set_visible(false);
                 }
 
                 self.previous_track.clone_from(&self.track);
-                self.track = track.1;
+                self.track = track;
             }
             CurrentTrackMessage::PositionChanged(position) => {
                 if let Some(track) = self.track.as_mut() {
                     let value = (position as f64 / track.duration as f64).clamp(0.0, 1.0);
                     let value = if value.is_nan() { 0.0 } else { value };
 
-                    popover.progress.set_fraction(value);
-
-                    progress.set_value(value * 100.0);
+                    widgets.popover.progress.set_fraction(value);
+                    widgets.progress.set_value(value * 100.0);
 
                     track.position = position;
                 }
             }
             CurrentTrackMessage::PlayerRemoved => {
-                container.set_visible(false);
+                widgets.container.set_visible(false);
+            }
+            // Without a session bus there won't ever be a track, the popover
+            // would stay empty.
+            CurrentTrackMessage::Unavailable(reason) => {
+                root.set_popover(None::<&gtk::Popover>);
+                root.set_child(Some(&unavailable::icon()));
+
+                set_unavailable(root, Some(&reason));
             }
         }
     }
 }
+
+impl CurrentTrack {
+    // The album art scheme wins over the bar theme while a track is shown, it
+    // still follows the configured mode and contrast.
+    fn scheme(&self) -> Scheme {
+        self.source.map_or_else(theme, |source| {
+            theme::scheme_for(source, SchemeVariant::Content)
+        })
+    }
+}
+
+impl CurrentTrackWidgets {
+    fn apply_style(&self, theme: &Scheme) {
+        let transitions = theme::transitions();
+
+        self.popover.container.set_style(
+            Preset::Card
+                .style(theme)
+                .background_color(theme.surface_bright),
+        );
+
+        self.popover.progress.set_child_style(
+            self.popover.progress.first_child().unwrap(),
+            Style::new()
+                .background_color(theme.on_secondary_fixed_variant)
+                .shape(Shape::ExtraSmall)
+                .min_height(scaled(6)),
+        );
+
+        self.popover.progress.set_child_style(
+            self.popover
+                .progress
+                .first_child()
+                .unwrap()
+                .first_child()
+                .unwrap(),
+            Style::new()
+                .background_color(theme.secondary_fixed)
+                .shape(Shape::ExtraSmall)
+                .min_height(scaled(6)),
+        );
+
+        self.popover.artist.set_style(
+            Style::new()
+                .typescale(Typescale::BodyMedium)
+                .color(theme.secondary)
+                .transition(&[transitions.colors.for_property("color")]),
+        );
+        self.popover.title.set_style(
+            Style::new()
+                .color(theme.primary)
+                .transition(&[transitions.colors.for_property("color")])
+                .typescale(Typescale::TitleSmall),
+        );
+
+        // The pill fades into the colors of the album art.
+        self.container.set_style(
+            Preset::Chip
+                .style(theme)
+                .background_gradient(Gradient::linear(
+                    Direction::ToRight,
+                    theme.surface_container_highest,
+                    theme.primary_container,
+                ))
+                .transition(
+                    &transitions
+                        .colors
+                        .for_properties(&["background-color", "background-image"]),
+                ),
+        );
+
+        self.progress
+            .set_background_color(theme.on_primary.as_rgba());
+        self.progress.set_style(
+            Style::new()
+                .color(theme.primary)
+                .shape(Shape::Medium)
+                .min_size(scaled(24)),
+        );
+
+        self.artist.set_style(
+            Style::new()
+                .typescale(Typescale::BodyMedium)
+                .color(theme.secondary)
+                .transition(&[transitions.colors.for_property("color")]),
+        );
+        self.delimiter.set_style(
+            Style::new()
+                .typescale(Typescale::BodyMedium)
+                .color(theme.on_surface_variant)
+                .transition(&[transitions.colors.for_property("color")]),
+        );
+        self.title.set_style(
+            Style::new()
+                .margin(&Thickness::Right(scaled(8)))
+                .color(theme.primary)
+                .transition(&[transitions.colors.for_property("color")])
+                .typescale(Typescale::LabelLarge),
+        );
+    }
+}
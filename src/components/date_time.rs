@@ -1,92 +1,194 @@
+use std::num::NonZeroU64;
+
 use tokio::time::{sleep, Duration};
 
 use crate::{
-    styling::{border::BorderRadius, style::Style, thickness::Thickness, StyleExt},
-    theme,
+    components::{set_unavailable, ModuleInit},
+    styling::{
+        border::BorderRadius, length::px, style::Style, thickness::Thickness, token::Shape,
+        StyleExt,
+    },
+    theme::{self, theme, ThemeChanged},
 };
+use gtk::prelude::*;
 use relm4::{Component, ComponentParts, ComponentSender, RelmContainerExt};
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct DateTimeOptions {
+    pub time_format: String,
+    pub date_format: String,
+    // Seconds between updates, zero is rejected.
+    pub interval: NonZeroU64,
+}
+
+impl Default for DateTimeOptions {
+    fn default() -> Self {
+        Self {
+            time_format: "%H:%M".into(),
+            date_format: "%m.%d.%Y".into(),
+            interval: NonZeroU64::new(5).unwrap(),
+        }
+    }
+}
 
 pub struct DateTime {
-    data: glib::DateTime,
+    options: DateTimeOptions,
 }
 
 pub struct DateTimeWidgets {
     date: gtk::Label,
     time: gtk::Label,
+    orientation: gtk::Orientation,
 }
 
 impl Component for DateTime {
     type Root = gtk::Box;
     type Widgets = DateTimeWidgets;
 
-    type CommandOutput = glib::DateTime;
-    type Input = ();
+    type CommandOutput = Result<glib::DateTime, String>;
+    type Input = ThemeChanged;
     type Output = ();
-    type Init = ();
+    type Init = ModuleInit<DateTimeOptions>;
 
     fn init_root() -> Self::Root {
-        Self::Root::new(gtk::Orientation::Horizontal, 2)
+        let root = Self::Root::new(gtk::Orientation::Horizontal, 2);
+
+        root.set_css_classes(&["date-time"]);
+
+        root
     }
 
     fn init(
-        (): Self::Init,
+        ModuleInit { options, context }: Self::Init,
         root: Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
-        let model = Self {
-            data: glib::DateTime::now_local().unwrap(),
-        };
+        let interval = options.interval;
+        let model = Self { options };
 
-        sender.command(|out, shutdown| {
+        sender.command(move |out, shutdown| {
             shutdown
                 .register(async move {
                     loop {
-                        sleep(Duration::from_secs(5)).await;
+                        sleep(Duration::from_secs(interval.get())).await;
 
-                        out.send(glib::DateTime::now_local().unwrap()).unwrap();
+                        if out.send(now()).is_err() {
+                            break;
+                        }
                     }
                 })
                 .drop_on_shutdown()
         });
 
-        let time = gtk::Label::new(Some(&model.data.format("%H:%M").unwrap()));
-        let date = gtk::Label::new(Some(&model.data.format("%m.%d.%Y").unwrap()));
-
-        time.set_style(
-            Style::new()
-                .background_color(theme().tertiary_container)
-                .color(theme().on_tertiary_container)
-                .padding(&Thickness::Custom(0, 4, 0, 4))
-                .border_radius(&BorderRadius::Custom(12, 4, 4, 12)),
-        );
-
-        date.set_style(
-            Style::new()
-                .background_color(theme().tertiary_container)
-                .color(theme().on_tertiary_container)
-                .padding(&Thickness::Custom(0, 4, 0, 4))
-                .border_radius(&BorderRadius::Custom(4, 12, 12, 4)),
-        );
+        let time = gtk::Label::default();
+        let date = gtk::Label::default();
+
+        time.set_css_classes(&["time"]);
+        date.set_css_classes(&["date"]);
 
+        root.set_orientation(context.orientation);
         root.container_add(&time);
         root.container_add(&date);
 
-        ComponentParts {
-            model,
-            widgets: Self::Widgets { date, time },
-        }
+        let widgets = Self::Widgets {
+            date,
+            time,
+            orientation: context.orientation,
+        };
+
+        widgets.show(&model.options, &root, now());
+        widgets.apply_style();
+
+        theme::subscribe(sender.input_sender(), |_| ThemeChanged);
+
+        ComponentParts { model, widgets }
     }
 
-    fn update_cmd_with_view(
+    fn update_with_view(
         &mut self,
         widgets: &mut Self::Widgets,
-        message: Self::CommandOutput,
+        ThemeChanged: Self::Input,
         _: ComponentSender<Self>,
         _: &Self::Root,
     ) {
-        widgets.time.set_label(&message.format("%H:%M").unwrap());
-        widgets.date.set_label(&message.format("%m.%d.%Y").unwrap());
+        widgets.apply_style();
+    }
 
-        self.data = message;
+    fn update_cmd_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        now: Self::CommandOutput,
+        _: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        widgets.show(&self.options, root, now);
     }
 }
+
+impl DateTimeWidgets {
+    fn show(
+        &self,
+        options: &DateTimeOptions,
+        root: &gtk::Box,
+        now: Result<glib::DateTime, String>,
+    ) {
+        let labels = now.and_then(|now| {
+            Ok((
+                format(&now, &options.time_format)?,
+                format(&now, &options.date_format)?,
+            ))
+        });
+
+        match labels {
+            Ok((time, date)) => {
+                self.time.set_label(&time);
+                self.date.set_label(&date);
+
+                set_unavailable(root, None);
+            }
+            Err(error) => {
+                self.time.set_label("--:--");
+                self.date.set_label("");
+
+                set_unavailable(root, Some(&error));
+            }
+        }
+    }
+
+    fn apply_style(&self) {
+        let theme = theme();
+
+        // Time and date read as one chip split in two halves, rounded on the
+        // outside and squared where they meet.
+        let half = Style::new()
+            .background_color(theme.tertiary_container)
+            .color(theme.on_tertiary_container)
+            .padding(&Thickness::Custom(px(0), px(4), px(0), px(4)));
+        let (outer, inner) = (Shape::Medium.radius(), Shape::ExtraSmall.radius());
+        let (time, date) = if self.orientation == gtk::Orientation::Vertical {
+            (
+                BorderRadius::Custom(outer, outer, inner, inner),
+                BorderRadius::Custom(inner, inner, outer, outer),
+            )
+        } else {
+            (
+                BorderRadius::Custom(outer, inner, inner, outer),
+                BorderRadius::Custom(inner, outer, outer, inner),
+            )
+        };
+
+        self.time.set_style(half.clone().border_radius(&time));
+        self.date.set_style(half.border_radius(&date));
+    }
+}
+
+fn now() -> Result<glib::DateTime, String> {
+    glib::DateTime::now_local().map_err(|error| format!("failed to read the local time: {error}"))
+}
+
+fn format(time: &glib::DateTime, format: &str) -> Result<glib::GString, String> {
+    time.format(format)
+        .map_err(|_| format!("invalid date format `{format}`"))
+}
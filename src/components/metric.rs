@@ -1,14 +1,40 @@
+use std::num::NonZeroU64;
+
 use crate::utils::{ColorExt, SystemExt};
 use crate::{
-    styling::{border::BorderRadius, style::Style, thickness::Thickness, StyleExt},
-    theme,
+    components::ModuleInit,
+    styling::{
+        length::px,
+        preset::Preset,
+        style::Style,
+        thickness::Thickness,
+        token::{Shape, Typescale},
+        StyleExt,
+    },
+    theme::{self, theme, ThemeChanged},
     widgets::CircularProgress,
 };
 use gtk::prelude::*;
 use relm4::{Component, ComponentParts, ComponentSender, RelmContainerExt};
+use serde::Deserialize;
 use sysinfo::{CpuRefreshKind, MemoryRefreshKind, RefreshKind, System};
 use tokio::time::{sleep, Duration};
 
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct MetricsOptions {
+    // Seconds between updates, zero is rejected.
+    pub interval: NonZeroU64,
+}
+
+impl Default for MetricsOptions {
+    fn default() -> Self {
+        Self {
+            interval: NonZeroU64::new(2).unwrap(),
+        }
+    }
+}
+
 pub struct Metrics {
     system: System,
     cpu: f64,
@@ -25,16 +51,20 @@ impl Component for Metrics {
     type Widgets = MetricsWidgets;
 
     type CommandOutput = ();
-    type Input = ();
+    type Input = ThemeChanged;
     type Output = ();
-    type Init = ();
+    type Init = ModuleInit<MetricsOptions>;
 
     fn init_root() -> Self::Root {
-        Self::Root::new(gtk::Orientation::Horizontal, 2)
+        let root = Self::Root::new(gtk::Orientation::Horizontal, 2);
+
+        root.set_css_classes(&["metrics"]);
+
+        root
     }
 
     fn init(
-        (): Self::Init,
+        ModuleInit { options, context }: Self::Init,
         root: Self::Root,
         sender: ComponentSender<Self>,
     ) -> ComponentParts<Self> {
@@ -50,13 +80,15 @@ impl Component for Metrics {
             system,
         };
 
-        sender.command(|out, shutdown| {
+        sender.command(move |out, shutdown| {
             shutdown
                 .register(async move {
                     loop {
-                        sleep(Duration::from_secs(2)).await;
+                        sleep(Duration::from_secs(options.interval.get())).await;
 
-                        out.send(()).unwrap();
+                        if out.send(()).is_err() {
+                            break;
+                        }
                     }
                 })
                 .drop_on_shutdown()
@@ -65,15 +97,32 @@ impl Component for Metrics {
         let (cpu_container, cpu) = view(&MetricKind::Cpu, model.cpu);
         let (memory_container, memory) = view(&MetricKind::Memory, model.memory);
 
+        root.set_orientation(context.orientation);
         root.container_add(&cpu_container);
         root.container_add(&memory_container);
 
+        apply_style(&cpu);
+        apply_style(&memory);
+
+        theme::subscribe(sender.input_sender(), |_| ThemeChanged);
+
         ComponentParts {
             model,
             widgets: Self::Widgets { cpu, memory },
         }
     }
 
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        ThemeChanged: Self::Input,
+        _: ComponentSender<Self>,
+        _: &Self::Root,
+    ) {
+        apply_style(&widgets.cpu);
+        apply_style(&widgets.memory);
+    }
+
     fn update_cmd_with_view(
         &mut self,
         widgets: &mut Self::Widgets,
@@ -102,39 +151,41 @@ pub enum MetricKind {
 fn view(kind: &MetricKind, value: f64) -> (gtk::Box, CircularProgress) {
     let container = gtk::Box::default();
 
+    container.set_css_classes(&[
+        "metric",
+        match kind {
+            MetricKind::Cpu => "cpu",
+            MetricKind::Memory => "memory",
+        },
+    ]);
     container.set_width_request(24);
     container.set_height_request(24);
-    container.set_style(
-        Style::new()
-            .background_color(theme().surface_container_highest)
-            .border_radius(&BorderRadius::All(12)),
-    );
 
     let progress = CircularProgress::default();
 
+    progress.add_css_class("progress");
     progress.set_value(value);
     progress.set_start_at(75.0);
     progress.set_thickness(2.0);
     progress.set_clockwise(true);
-    progress.set_background_color(theme().primary_container.as_rgba());
     progress.set_width_request(24);
     progress.set_height_request(24);
-    progress.set_style(
-        Style::new()
-            .color(theme().on_primary_container)
-            .border_radius(&BorderRadius::All(12)),
-    );
 
     let icon = gtk::Label::default();
 
+    icon.set_css_classes(&["icon"]);
     icon.set_label(match kind {
         MetricKind::Cpu => "",
         MetricKind::Memory => "",
     });
 
     icon.set_style(match kind {
-        MetricKind::Cpu => Style::new().font_size(14).padding(&Thickness::Right(6)),
-        MetricKind::Memory => Style::new().font_size(12).padding(&Thickness::Right(2)),
+        MetricKind::Cpu => Style::new()
+            .typescale(Typescale::BodyMedium)
+            .padding(&Thickness::Right(px(6))),
+        MetricKind::Memory => Style::new()
+            .typescale(Typescale::BodySmall)
+            .padding(&Thickness::Right(px(2))),
     });
 
     progress.set_child(icon);
@@ -143,3 +194,18 @@ fn view(kind: &MetricKind, value: f64) -> (gtk::Box, CircularProgress) {
 
     (container, progress)
 }
+
+fn apply_style(progress: &CircularProgress) {
+    let theme = theme();
+
+    if let Some(container) = progress.parent() {
+        container.set_style(Preset::Chip.style(&theme));
+    }
+
+    progress.set_background_color(theme.primary_container.as_rgba());
+    progress.set_style(
+        Style::new()
+            .color(theme.on_primary_container)
+            .shape(Shape::Medium),
+    );
+}
@@ -1,5 +1,14 @@
 pub mod current_track;
 pub mod date_time;
 pub mod metric;
+pub mod registry;
+pub mod unavailable;
 pub mod volume;
 pub mod workspace;
+pub mod workspaces;
+
+pub use registry::{
+    register_component, register_module, registry, BarModule, ModuleContext, ModuleInit,
+    ModuleRegistry,
+};
+pub use unavailable::{set_unavailable, unavailable_reason, Unavailable};
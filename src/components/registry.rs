@@ -0,0 +1,229 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{RwLock, RwLockReadGuard},
+};
+
+use gtk::prelude::*;
+use relm4::{once_cell::sync::Lazy, Component, ComponentController, Controller};
+use serde::de::DeserializeOwned;
+use tracing::warn;
+
+use crate::config::ModuleConfig;
+
+use super::{
+    current_track::CurrentTrack, date_time::DateTime, metric::Metrics, unavailable::Unavailable,
+    volume::Volume, workspaces::Workspaces,
+};
+
+static REGISTRY: Lazy<RwLock<ModuleRegistry>> =
+    Lazy::new(|| RwLock::new(ModuleRegistry::builtin()));
+
+pub trait BarModule {
+    fn widget(&self) -> gtk::Widget;
+}
+
+// Everything a module may need to know about the bar it is placed on.
+#[derive(Debug, Clone)]
+pub struct ModuleContext {
+    pub monitor: gdk::Monitor,
+    pub orientation: gtk::Orientation,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleInit<O> {
+    pub options: O,
+    pub context: ModuleContext,
+}
+
+impl<C: Component> BarModule for Controller<C>
+where
+    C::Root: IsA<gtk::Widget>,
+{
+    fn widget(&self) -> gtk::Widget {
+        ComponentController::widget(self).clone().upcast()
+    }
+}
+
+type Validator = Box<dyn Fn(&toml::Table) -> Result<(), toml::de::Error> + Send + Sync>;
+type Builder = Box<
+    dyn Fn(&toml::Table, &ModuleContext) -> Result<Box<dyn BarModule>, toml::de::Error>
+        + Send
+        + Sync,
+>;
+
+struct Entry {
+    validate: Validator,
+    build: Builder,
+}
+
+#[derive(Default)]
+pub struct ModuleRegistry {
+    entries: HashMap<String, Entry>,
+}
+
+#[derive(Debug)]
+pub enum ModuleError {
+    Unknown(String, Vec<String>),
+    InvalidOptions(String, toml::de::Error),
+}
+
+impl ModuleRegistry {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn builtin() -> Self {
+        let mut registry = Self::new();
+
+        registry.register_component::<Workspaces, _>("workspaces");
+        registry.register_component::<CurrentTrack, _>("current-track");
+        registry.register_component::<Volume, _>("volume");
+        registry.register_component::<Metrics, _>("metrics");
+        registry.register_component::<DateTime, _>("date-time");
+
+        registry
+    }
+
+    pub fn register<O, F>(&mut self, name: &str, build: F)
+    where
+        O: DeserializeOwned + 'static,
+        F: Fn(O, &ModuleContext) -> Box<dyn BarModule> + Send + Sync + 'static,
+    {
+        self.entries.insert(
+            name.into(),
+            Entry {
+                validate: Box::new(|options| parse_options::<O>(options).map(drop)),
+                build: Box::new(move |options, context| {
+                    parse_options(options).map(|options| build(options, context))
+                }),
+            },
+        );
+    }
+
+    pub fn register_component<C, O>(&mut self, name: &str)
+    where
+        C: Component<Init = ModuleInit<O>>,
+        C::Root: IsA<gtk::Widget>,
+        O: DeserializeOwned + 'static,
+    {
+        self.register(
+            name,
+            |options: O, context: &ModuleContext| -> Box<dyn BarModule> {
+                Box::new(
+                    C::builder()
+                        .launch(ModuleInit {
+                            options,
+                            context: context.clone(),
+                        })
+                        .detach(),
+                )
+            },
+        );
+    }
+
+    pub fn validate(&self, config: &ModuleConfig) -> Result<(), ModuleError> {
+        (self.entry(&config.kind)?.validate)(&config.options)
+            .map_err(|error| ModuleError::InvalidOptions(config.kind.clone(), error))
+    }
+
+    pub fn build(
+        &self,
+        config: &ModuleConfig,
+        context: &ModuleContext,
+    ) -> Result<Box<dyn BarModule>, ModuleError> {
+        let entry = self.entry(&config.kind)?;
+
+        // A module that panics while starting is replaced with a placeholder
+        // instead of taking the whole bar down with it.
+        match panic::catch_unwind(AssertUnwindSafe(|| (entry.build)(&config.options, context))) {
+            Ok(module) => {
+                module.map_err(|error| ModuleError::InvalidOptions(config.kind.clone(), error))
+            }
+            Err(payload) => {
+                let reason = format!(
+                    "`{}` failed to start: {}",
+                    config.kind,
+                    panic_message(payload.as_ref())
+                );
+
+                warn!(module = %config.kind, "{reason}");
+
+                Ok(Box::new(Unavailable::new(&reason)))
+            }
+        }
+    }
+
+    fn entry(&self, name: &str) -> Result<&Entry, ModuleError> {
+        self.entries.get(name).ok_or_else(|| {
+            let mut known = self.entries.keys().cloned().collect::<Vec<_>>();
+
+            known.sort();
+
+            ModuleError::Unknown(name.into(), known)
+        })
+    }
+}
+
+fn parse_options<O: DeserializeOwned>(options: &toml::Table) -> Result<O, toml::de::Error> {
+    options.clone().try_into()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown error".into())
+}
+
+#[must_use]
+pub fn registry() -> RwLockReadGuard<'static, ModuleRegistry> {
+    REGISTRY.read().unwrap()
+}
+
+pub fn register_module<O, F>(name: &str, build: F)
+where
+    O: DeserializeOwned + 'static,
+    F: Fn(O, &ModuleContext) -> Box<dyn BarModule> + Send + Sync + 'static,
+{
+    REGISTRY.write().unwrap().register(name, build);
+}
+
+pub fn register_component<C, O>(name: &str)
+where
+    C: Component<Init = ModuleInit<O>>,
+    C::Root: IsA<gtk::Widget>,
+    O: DeserializeOwned + 'static,
+{
+    REGISTRY.write().unwrap().register_component::<C, O>(name);
+}
+
+impl fmt::Display for ModuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(name, known) => write!(
+                f,
+                "unknown module `{name}`, expected one of {}",
+                known
+                    .iter()
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Self::InvalidOptions(name, error) => {
+                write!(
+                    f,
+                    "invalid options for module `{name}`: {}",
+                    error.message()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ModuleError {}
@@ -0,0 +1,72 @@
+use gtk::prelude::*;
+
+use super::BarModule;
+
+const ICON: &str = "\u{f071}";
+
+// Stands in for a module that failed to start, the rest of the bar keeps
+// working and the tooltip tells why this one doesn't.
+pub struct Unavailable {
+    label: gtk::Label,
+}
+
+impl Unavailable {
+    #[must_use]
+    pub fn new(reason: &str) -> Self {
+        let label = icon();
+
+        set_unavailable(&label, Some(reason));
+
+        Self { label }
+    }
+}
+
+impl BarModule for Unavailable {
+    fn widget(&self) -> gtk::Widget {
+        self.label.clone().upcast()
+    }
+}
+
+#[must_use]
+pub fn icon() -> gtk::Label {
+    let label = gtk::Label::new(Some(ICON));
+
+    label.set_css_classes(&["icon"]);
+
+    label
+}
+
+// Marks a module whose backend failed, `None` clears the mark once it works
+// again.
+pub fn set_unavailable(widget: &impl IsA<gtk::Widget>, reason: Option<&str>) {
+    if reason.is_some() {
+        widget.add_css_class("unavailable");
+        widget.set_opacity(0.5);
+    } else {
+        widget.remove_css_class("unavailable");
+        widget.set_opacity(1.0);
+    }
+
+    widget.set_tooltip_text(reason);
+}
+
+// Why the module in `widget` is unavailable, the mark may be on the whole
+// module or on one of its parts.
+#[must_use]
+pub fn unavailable_reason(widget: &gtk::Widget) -> Option<String> {
+    if widget.has_css_class("unavailable") {
+        return Some(widget.tooltip_text().map_or_else(String::new, Into::into));
+    }
+
+    let mut child = widget.first_child();
+
+    while let Some(widget) = child {
+        if let Some(reason) = unavailable_reason(&widget) {
+            return Some(reason);
+        }
+
+        child = widget.next_sibling();
+    }
+
+    None
+}
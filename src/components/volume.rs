@@ -1,89 +1,104 @@
 use crate::{
-    styling::{border::BorderRadius, style::Style, thickness::Thickness, StyleExt},
-    theme,
+    components::{set_unavailable, ModuleInit},
+    data::volume::SinkVolume,
+    styling::{
+        length::px, preset::Preset, style::Style, thickness::Thickness, token::Shape, StyleExt,
+    },
+    theme::{self, theme, ThemeChanged},
 };
 use gtk::prelude::*;
 use relm4::{Component, ComponentParts, ComponentSender, RelmContainerExt};
-use std::process::Command;
+use serde::Deserialize;
+use tracing::warn;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct VolumeOptions {
+    pub sink: String,
+}
+
+impl Default for VolumeOptions {
+    fn default() -> Self {
+        Self {
+            sink: "@DEFAULT_AUDIO_SINK@".into(),
+        }
+    }
+}
 
 pub struct Volume;
 
+pub struct VolumeWidgets {
+    slider: gtk::Scale,
+    orientation: gtk::Orientation,
+}
+
 impl Component for Volume {
     type Root = gtk::Box;
-    type Widgets = ();
+    type Widgets = VolumeWidgets;
 
     type CommandOutput = ();
-    type Input = ();
+    type Input = ThemeChanged;
     type Output = ();
-    type Init = ();
+    type Init = ModuleInit<VolumeOptions>;
 
     fn init_root() -> Self::Root {
         Self::Root::default()
     }
 
-    fn init((): Self::Init, root: Self::Root, _: ComponentSender<Self>) -> ComponentParts<Self> {
+    fn init(
+        ModuleInit { options, context }: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
         let model = Self;
 
+        theme::subscribe(sender.input_sender(), |_| ThemeChanged);
+
         root.set_css_classes(&["volume"]);
-        root.set_orientation(gtk::Orientation::Horizontal);
+        root.set_orientation(context.orientation);
         root.set_spacing(12);
 
-        root.set_style(
-            Style::new()
-                .margin(&Thickness::Left(4))
-                .background_color(theme().surface_container_highest)
-                .color(theme().secondary)
-                .min_width(100)
-                .border_radius(&BorderRadius::All(32))
-                .padding(&Thickness::Custom(0, 8, 0, 8)),
-        );
-
         let icon = gtk::Label::default();
 
+        icon.set_css_classes(&["icon"]);
         icon.set_label("");
 
         let slider = gtk::Scale::default();
 
+        slider.add_css_class("slider");
         slider.set_range(0.0, 1.0);
-        slider.set_value(
-            String::from_utf8(
-                Command::new("wpctl")
-                    .arg("get-volume")
-                    .arg("@DEFAULT_AUDIO_SINK@")
-                    .output()
-                    .unwrap()
-                    .stdout,
-            )
-            .unwrap()
-            .trim()[9..12]
-                .parse()
-                .unwrap(),
-        );
-        slider.set_hexpand(true);
 
-        slider.set_child_style(
-            slider.first_child().unwrap(),
-            Style::new()
-                .background_color(theme().on_secondary_fixed_variant)
-                .border_radius(&BorderRadius::All(50))
-                .min_height(6)
-                .min_width(50),
-        );
+        match SinkVolume::get(&options.sink) {
+            Ok(volume) => {
+                slider.set_value(volume.level);
 
-        slider.set_child_style(
-            slider.first_child().unwrap().first_child().unwrap(),
-            Style::new()
-                .background_color(theme().secondary_fixed)
-                .border_radius(&BorderRadius::All(10)),
-        );
+                if volume.muted {
+                    icon.set_label("");
+                }
+            }
+            Err(error) => {
+                warn!(sink = %options.sink, "{error}");
+
+                slider.set_sensitive(false);
+                set_unavailable(&root, Some(&error.to_string()));
+            }
+        }
+
+        slider.set_orientation(context.orientation);
 
-        slider.connect_change_value(|_, _, value| {
-            Command::new("wpctl")
-                .arg("set-volume")
-                .arg("@DEFAULT_AUDIO_SINK@")
-                .arg(value.to_string())
-                .spawn()
-                .unwrap();
+        if context.orientation == gtk::Orientation::Vertical {
+            slider.set_inverted(true);
+            slider.set_vexpand(true);
+        } else {
+            slider.set_hexpand(true);
+        }
+
+        slider.connect_change_value(move |slider, _, value| {
+            if let Err(error) = SinkVolume::set(&options.sink, value) {
+                warn!(sink = %options.sink, "{error}");
+
+                set_unavailable(slider, Some(&error.to_string()));
+            }
 
             glib::Propagation::Proceed
         });
@@ -91,6 +106,68 @@ impl Component for Volume {
         root.container_add(&icon);
         root.container_add(&slider);
 
-        ComponentParts { model, widgets: () }
+        let widgets = Self::Widgets {
+            slider,
+            orientation: context.orientation,
+        };
+
+        widgets.apply_style(&root);
+
+        ComponentParts { model, widgets }
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        ThemeChanged: Self::Input,
+        _: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        widgets.apply_style(root);
+    }
+}
+
+impl VolumeWidgets {
+    fn apply_style(&self, root: &gtk::Box) {
+        let theme = theme();
+
+        let (root_style, trough_style) = if self.orientation == gtk::Orientation::Vertical {
+            (
+                Style::new()
+                    .margin(&Thickness::Top(px(4)))
+                    .min_height(100)
+                    .padding(&Thickness::Custom(px(8), px(0), px(8), px(0))),
+                Style::new().min_width(6).min_height(50),
+            )
+        } else {
+            (
+                Style::new()
+                    .margin(&Thickness::Left(px(4)))
+                    .min_width(100)
+                    .padding(&Thickness::Custom(px(0), px(8), px(0), px(8))),
+                Style::new().min_height(6).min_width(50),
+            )
+        };
+
+        root.set_style(
+            Preset::Pill
+                .style(&theme)
+                .merge(&root_style)
+                .color(theme.secondary),
+        );
+
+        self.slider.set_child_style(
+            self.slider.first_child().unwrap(),
+            trough_style
+                .background_color(theme.on_secondary_fixed_variant)
+                .shape(Shape::Full),
+        );
+
+        self.slider.set_child_style(
+            self.slider.first_child().unwrap().first_child().unwrap(),
+            Style::new()
+                .background_color(theme.secondary_fixed)
+                .shape(Shape::Full),
+        );
     }
 }
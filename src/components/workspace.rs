@@ -1,7 +1,14 @@
 use crate::{
     data::workspace::{Workspace, WorkspacePosition},
-    styling::{border::BorderRadius, style::Style, stylesheet::StyleSheet, StyleExt},
-    theme,
+    styling::{
+        border::BorderRadius,
+        selector::Selector,
+        style::Style,
+        stylesheet::StyleSheet,
+        token::{Shape, Typescale},
+        StyleExt,
+    },
+    theme::{self, theme, ThemeChanged},
     utils::{clsx, ColorExt},
 };
 use gtk::prelude::*;
@@ -10,13 +17,20 @@ use relm4::{
     FactorySender,
 };
 
+// A workspace shown in a bar with the given orientation, the first and last
+// ones are rounded on the outer ends of the row or column.
+pub struct WorkspaceItem {
+    pub workspace: Workspace,
+    pub orientation: gtk::Orientation,
+}
+
 pub struct WorkspaceWidgets {
     workspace: gtk::Label,
 }
 
-impl FactoryComponent for Workspace {
+impl FactoryComponent for WorkspaceItem {
     type Init = Self;
-    type Input = ();
+    type Input = ThemeChanged;
     type Output = ();
     type CommandOutput = ();
     type ParentWidget = gtk::Box;
@@ -35,74 +49,39 @@ impl FactoryComponent for Workspace {
         _returned_widget: &<Self::ParentWidget as FactoryView>::ReturnedWidget,
         _sender: FactorySender<Self>,
     ) -> Self::Widgets {
-        workspace.set_label(&self.id.to_string());
-        workspace.set_css_classes(&clsx(vec![
-            (self.exists, "exists"),
-            (self.active, "active"),
-            (
-                matches!(
-                    self.position,
-                    WorkspacePosition::First | WorkspacePosition::Both
-                ),
-                "first",
-            ),
-            (
-                matches!(
-                    self.position,
-                    WorkspacePosition::Last | WorkspacePosition::Both
-                ),
-                "last",
-            ),
-        ]));
-        workspace.set_stylesheet(
-            StyleSheet::new()
-                .default_style(
-                    Style::new()
-                        .transition("background-color 0.3s, color 0.3s, border-radius 0.3s")
-                        .min_width(19)
-                        .font_size(12)
-                        .color(theme().secondary.with_alpha(0.5))
-                        .background_color(theme().on_secondary.with_alpha(0.5)),
-                )
-                .style_for(
-                    ".exists",
-                    Style::new()
-                        .color(theme().secondary)
-                        .background_color(theme().on_secondary),
-                )
-                .style_for(
-                    ".first",
-                    Style::new()
-                        .border_radius(&BorderRadius::TopLeft(32))
-                        .border_radius(&BorderRadius::BottomLeft(32)),
-                )
-                .style_for(
-                    ".last",
-                    Style::new()
-                        .border_radius(&BorderRadius::TopRight(32))
-                        .border_radius(&BorderRadius::BottomRight(32)),
-                )
-                .style_for(
-                    ".active",
-                    Style::new()
-                        .color(theme().primary_container)
-                        .background_color(theme().primary),
-                ),
-        );
+        workspace.set_label(&self.workspace.id.to_string());
+        workspace.set_css_classes(&self.workspace.css_classes());
+        workspace.set_stylesheet(stylesheet(self.orientation));
 
         Self::Widgets { workspace }
     }
 
-    fn init_model(
-        workspace: Self::Init,
-        _index: &DynamicIndex,
+    fn init_model(item: Self::Init, _index: &DynamicIndex, _sender: FactorySender<Self>) -> Self {
+        item
+    }
+
+    fn update_with_view(
+        &mut self,
+        widgets: &mut Self::Widgets,
+        ThemeChanged: Self::Input,
         _sender: FactorySender<Self>,
-    ) -> Self {
-        workspace
+    ) {
+        widgets
+            .workspace
+            .set_stylesheet(stylesheet(self.orientation));
     }
 
     fn update_view(&self, widgets: &mut Self::Widgets, _sender: FactorySender<Self>) {
-        widgets.workspace.set_css_classes(&clsx(vec![
+        widgets
+            .workspace
+            .set_css_classes(&self.workspace.css_classes());
+    }
+}
+
+impl Workspace {
+    fn css_classes(&self) -> Vec<&'static str> {
+        clsx(vec![
+            (true, "workspace"),
             (self.exists, "exists"),
             (self.active, "active"),
             (
@@ -119,6 +98,63 @@ impl FactoryComponent for Workspace {
                 ),
                 "last",
             ),
-        ]));
+        ])
     }
 }
+
+fn stylesheet(orientation: gtk::Orientation) -> StyleSheet {
+    let theme = theme();
+    let transitions = theme::transitions();
+    let full = Shape::Full.radius();
+    let (first, last) = if orientation == gtk::Orientation::Vertical {
+        (
+            [BorderRadius::TopLeft(full), BorderRadius::TopRight(full)],
+            [
+                BorderRadius::BottomLeft(full),
+                BorderRadius::BottomRight(full),
+            ],
+        )
+    } else {
+        (
+            [BorderRadius::TopLeft(full), BorderRadius::BottomLeft(full)],
+            [
+                BorderRadius::TopRight(full),
+                BorderRadius::BottomRight(full),
+            ],
+        )
+    };
+
+    StyleSheet::new()
+        .default_style(
+            Style::new()
+                .transition(&transitions.state.for_properties(&[
+                    "background-color",
+                    "color",
+                    "border-radius",
+                ]))
+                .min_width(19)
+                .typescale(Typescale::BodySmall)
+                .color(theme.secondary.with_alpha(0.5))
+                .background_color(theme.on_secondary.with_alpha(0.5)),
+        )
+        .style_for(
+            Selector::new().class("exists"),
+            Style::new()
+                .color(theme.secondary)
+                .background_color(theme.on_secondary),
+        )
+        .style_for(Selector::new().class("first"), rounded(&first))
+        .style_for(Selector::new().class("last"), rounded(&last))
+        .style_for(
+            Selector::new().class("active"),
+            Style::new()
+                .color(theme.primary_container)
+                .background_color(theme.primary),
+        )
+}
+
+fn rounded(corners: &[BorderRadius]) -> Style {
+    corners
+        .iter()
+        .fold(Style::new(), |style, corner| style.border_radius(corner))
+}
@@ -0,0 +1,193 @@
+use crate::{
+    components::{set_unavailable, workspace::WorkspaceItem, ModuleInit},
+    data::workspace::Workspace,
+    styling::{length::px, preset::Preset, thickness::Thickness, StyleExt},
+    theme::{self, theme, ThemeChanged},
+};
+use gtk::prelude::*;
+use hyprland::event_listener::EventListener;
+use relm4::{factory::FactoryVecDeque, Component, ComponentParts, ComponentSender};
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct WorkspacesOptions {
+    pub count: usize,
+}
+
+impl Default for WorkspacesOptions {
+    fn default() -> Self {
+        Self { count: 10 }
+    }
+}
+
+pub struct Workspaces {
+    options: WorkspacesOptions,
+    monitor: Option<String>,
+    orientation: gtk::Orientation,
+    workspaces: FactoryVecDeque<WorkspaceItem>,
+}
+
+#[derive(Debug)]
+pub enum WorkspacesMessage {
+    Changed,
+    Unavailable(String),
+}
+
+impl Component for Workspaces {
+    type Root = gtk::Box;
+    type Widgets = ();
+
+    type CommandOutput = WorkspacesMessage;
+    type Input = ThemeChanged;
+    type Output = ();
+    type Init = ModuleInit<WorkspacesOptions>;
+
+    fn init_root() -> Self::Root {
+        Self::Root::new(gtk::Orientation::Horizontal, 2)
+    }
+
+    fn init(
+        ModuleInit { options, context }: Self::Init,
+        root: Self::Root,
+        sender: ComponentSender<Self>,
+    ) -> ComponentParts<Self> {
+        let monitor = context.monitor.connector().map(Into::into);
+        let mut workspaces = FactoryVecDeque::builder().launch(root.clone()).detach();
+        let mut guard = workspaces.guard();
+
+        for workspace in Workspace::all(options.count, monitor.as_deref()) {
+            guard.push_back(WorkspaceItem {
+                workspace,
+                orientation: context.orientation,
+            });
+        }
+
+        guard.drop();
+
+        let model = Self {
+            options,
+            monitor,
+            orientation: context.orientation,
+            workspaces,
+        };
+
+        sender.command(|out, shutdown| {
+            shutdown
+                .register(async move {
+                    let mut event_listener = EventListener::new();
+
+                    {
+                        let out = out.clone();
+
+                        event_listener.add_workspace_added_handler(move |_| {
+                            let _ = out.send(WorkspacesMessage::Changed);
+                        });
+                    }
+
+                    {
+                        let out = out.clone();
+
+                        event_listener.add_workspace_destroy_handler(move |_| {
+                            let _ = out.send(WorkspacesMessage::Changed);
+                        });
+                    }
+
+                    {
+                        let out = out.clone();
+
+                        event_listener.add_workspace_change_handler(move |_| {
+                            let _ = out.send(WorkspacesMessage::Changed);
+                        });
+                    }
+
+                    {
+                        let out = out.clone();
+
+                        event_listener.add_workspace_moved_handler(move |_| {
+                            let _ = out.send(WorkspacesMessage::Changed);
+                        });
+                    }
+
+                    {
+                        let out = out.clone();
+
+                        event_listener.add_active_monitor_change_handler(move |_| {
+                            let _ = out.send(WorkspacesMessage::Changed);
+                        });
+                    }
+
+                    if let Err(error) = event_listener.start_listener_async().await {
+                        let reason = format!("failed to listen to Hyprland events: {error}");
+
+                        warn!("{reason}");
+
+                        let _ = out.send(WorkspacesMessage::Unavailable(reason));
+                    }
+                })
+                .drop_on_shutdown()
+        });
+
+        theme::subscribe(sender.input_sender(), |_| ThemeChanged);
+
+        root.set_css_classes(&["workspaces"]);
+        root.set_orientation(context.orientation);
+
+        apply_style(&root);
+
+        ComponentParts { model, widgets: () }
+    }
+
+    fn update(&mut self, ThemeChanged: Self::Input, _: ComponentSender<Self>, root: &Self::Root) {
+        apply_style(root);
+
+        self.workspaces.broadcast(ThemeChanged);
+    }
+
+    fn update_cmd(
+        &mut self,
+        message: Self::CommandOutput,
+        _: ComponentSender<Self>,
+        root: &Self::Root,
+    ) {
+        match message {
+            WorkspacesMessage::Changed => {
+                debug!(monitor = ?self.monitor, "workspaces changed");
+
+                let mut guard = self.workspaces.guard();
+                let workspaces = Workspace::all(self.options.count, self.monitor.as_deref());
+
+                // The amount of workspaces shown changes as they move between monitors.
+                while guard.len() > workspaces.len() {
+                    guard.pop_back();
+                }
+
+                for (index, workspace) in workspaces.into_iter().enumerate() {
+                    if let Some(item) = guard.get_mut(index) {
+                        item.workspace = workspace;
+                    } else {
+                        guard.push_back(WorkspaceItem {
+                            workspace,
+                            orientation: self.orientation,
+                        });
+                    }
+                }
+            }
+            WorkspacesMessage::Unavailable(reason) => set_unavailable(root, Some(&reason)),
+        }
+    }
+}
+
+fn apply_style(root: &gtk::Box) {
+    let theme = theme();
+
+    root.set_style(
+        Preset::Pill
+            .style(&theme)
+            .background_color(theme.surface)
+            .margin(&Thickness::Right(px(4)))
+            .padding(&Thickness::All(px(4)))
+            .min_height(8),
+    );
+}
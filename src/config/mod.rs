@@ -0,0 +1,321 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use material_colors::color::Argb;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{
+    components::registry::{registry, ModuleError},
+    styling::{token::Scale, transition::Transitions},
+    theme::{Contrast, Mode, SchemeOptions, SchemeVariant},
+};
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    #[serde(skip)]
+    pub path: PathBuf,
+    pub bar: BarConfig,
+    pub theme: ThemeConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct BarConfig {
+    pub edge: BarEdge,
+    pub layer: BarLayer,
+    pub exclusive_zone: ExclusiveZone,
+    pub margin: Margins,
+    pub stretch: bool,
+    pub start: Vec<ModuleConfig>,
+    pub center: Vec<ModuleConfig>,
+    pub end: Vec<ModuleConfig>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BarEdge {
+    #[default]
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BarLayer {
+    Background,
+    Bottom,
+    #[default]
+    Top,
+    Overlay,
+}
+
+// `auto` reserves exactly the space the bar takes, a size reserves that many
+// pixels and `0` lets windows go under the bar.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExclusiveZone {
+    #[default]
+    Auto,
+    Size(i32),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Margins {
+    pub top: i32,
+    pub right: i32,
+    pub bottom: i32,
+    pub left: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ModuleConfig {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(flatten)]
+    pub options: toml::Table,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ThemeConfig {
+    pub source: Option<Color>,
+    pub wallpaper: Option<PathBuf>,
+    pub mode: Mode,
+    pub variant: SchemeVariant,
+    pub contrast: Contrast,
+    pub transitions: Transitions,
+    pub scale: Scale,
+}
+
+// Theme options given on the command line, they are applied on top of the
+// configuration every time it's loaded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ThemeOverrides {
+    pub source: Option<Color>,
+    pub wallpaper: Option<PathBuf>,
+    pub mode: Option<Mode>,
+    pub variant: Option<SchemeVariant>,
+    pub contrast: Option<Contrast>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color(pub Argb);
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Read(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+    Module(PathBuf, ModuleError),
+}
+
+impl Default for BarConfig {
+    fn default() -> Self {
+        Self {
+            edge: BarEdge::default(),
+            layer: BarLayer::default(),
+            exclusive_zone: ExclusiveZone::default(),
+            margin: Margins::default(),
+            stretch: true,
+            start: vec![ModuleConfig::new("workspaces")],
+            center: vec![ModuleConfig::new("current-track")],
+            end: vec![
+                ModuleConfig::new("volume"),
+                ModuleConfig::new("metrics"),
+                ModuleConfig::new("date-time"),
+            ],
+        }
+    }
+}
+
+impl Config {
+    #[must_use]
+    pub fn directory() -> PathBuf {
+        glib::user_config_dir().join("barry")
+    }
+
+    #[must_use]
+    pub fn path() -> PathBuf {
+        Self::directory().join("config.toml")
+    }
+
+    pub fn load() -> Result<Self, ConfigError> {
+        Self::load_from(&Self::path())
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self, ConfigError> {
+        let mut config = match fs::read_to_string(path) {
+            Ok(data) => toml::from_str::<Self>(&data)
+                .map_err(|error| ConfigError::Parse(path.into(), error))?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(error) => return Err(ConfigError::Read(path.into(), error)),
+        };
+
+        config.path = path.into();
+
+        let registry = registry();
+
+        for module in config.bar.modules() {
+            registry
+                .validate(module)
+                .map_err(|error| ConfigError::Module(path.into(), error))?;
+        }
+
+        Ok(config)
+    }
+}
+
+impl BarConfig {
+    pub fn modules(&self) -> impl Iterator<Item = &ModuleConfig> {
+        self.start.iter().chain(&self.center).chain(&self.end)
+    }
+}
+
+impl BarEdge {
+    pub const ALL: [Self; 4] = [Self::Top, Self::Bottom, Self::Left, Self::Right];
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Top => Self::Bottom,
+            Self::Bottom => Self::Top,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+
+    #[must_use]
+    pub const fn is_vertical(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+            Self::Left => "left",
+            Self::Right => "right",
+        }
+    }
+}
+
+impl Margins {
+    #[must_use]
+    pub const fn get(&self, edge: BarEdge) -> i32 {
+        match edge {
+            BarEdge::Top => self.top,
+            BarEdge::Bottom => self.bottom,
+            BarEdge::Left => self.left,
+            BarEdge::Right => self.right,
+        }
+    }
+}
+
+impl ThemeConfig {
+    #[must_use]
+    pub const fn scheme_options(&self) -> SchemeOptions {
+        SchemeOptions {
+            mode: self.mode,
+            variant: self.variant,
+            contrast: self.contrast,
+        }
+    }
+}
+
+impl ThemeOverrides {
+    // A source color replaces the configured wallpaper, a wallpaper still wins
+    // over any color.
+    pub fn apply(&self, config: &mut ThemeConfig) {
+        if let Some(source) = self.source {
+            config.source = Some(source);
+            config.wallpaper = None;
+        }
+
+        if let Some(wallpaper) = &self.wallpaper {
+            config.wallpaper = Some(wallpaper.clone());
+        }
+
+        if let Some(mode) = self.mode {
+            config.mode = mode;
+        }
+
+        if let Some(variant) = self.variant {
+            config.variant = variant;
+        }
+
+        if let Some(contrast) = self.contrast {
+            config.contrast = contrast;
+        }
+    }
+}
+
+impl ModuleConfig {
+    #[must_use]
+    pub fn new(kind: &str) -> Self {
+        Self {
+            kind: kind.into(),
+            options: toml::Table::new(),
+        }
+    }
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Argb::from_str(value)
+            .map(Self)
+            .map_err(|_| format!("invalid color `{value}`, expected a hex color like `#2C563E`"))
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for ExclusiveZone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Value {
+            Keyword(String),
+            Size(i32),
+        }
+
+        match Value::deserialize(deserializer)? {
+            Value::Keyword(keyword) if keyword == "auto" => Ok(Self::Auto),
+            Value::Size(size) => Ok(Self::Size(size)),
+            Value::Keyword(keyword) => Err(de::Error::custom(format!(
+                "invalid exclusive zone `{keyword}`, expected `auto` or a size in pixels"
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path, error) => {
+                write!(f, "failed to read config {}: {error}", path.display())
+            }
+            Self::Parse(path, error) => {
+                write!(f, "invalid config {}: {error}", path.display())
+            }
+            Self::Module(path, error) => {
+                write!(f, "invalid config {}: {error}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
@@ -1,2 +1,3 @@
 pub mod track;
+pub mod volume;
 pub mod workspace;
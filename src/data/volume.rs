@@ -0,0 +1,102 @@
+use std::{fmt, io, process::Command};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SinkVolume {
+    pub level: f64,
+    pub muted: bool,
+}
+
+#[derive(Debug)]
+pub enum VolumeError {
+    Spawn(io::Error),
+    Failed(String),
+    Parse(String),
+}
+
+impl SinkVolume {
+    pub fn get(sink: &str) -> Result<Self, VolumeError> {
+        let output = Command::new("wpctl")
+            .arg("get-volume")
+            .arg(sink)
+            .output()
+            .map_err(VolumeError::Spawn)?;
+
+        if !output.status.success() {
+            return Err(VolumeError::Failed(
+                String::from_utf8_lossy(&output.stderr).trim().into(),
+            ));
+        }
+
+        let output = String::from_utf8_lossy(&output.stdout);
+
+        Self::parse(&output).ok_or_else(|| VolumeError::Parse(output.trim().into()))
+    }
+
+    pub fn set(sink: &str, level: f64) -> Result<(), VolumeError> {
+        Command::new("wpctl")
+            .arg("set-volume")
+            .arg(sink)
+            .arg(level.to_string())
+            .spawn()
+            .map(drop)
+            .map_err(VolumeError::Spawn)
+    }
+
+    // `wpctl get-volume` prints `Volume: 0.40`, followed by `[MUTED]` when the
+    // sink is muted.
+    fn parse(output: &str) -> Option<Self> {
+        let mut words = output.trim().strip_prefix("Volume:")?.split_whitespace();
+        let level = words.next()?.parse().ok()?;
+
+        Some(Self {
+            level,
+            muted: words.any(|word| word == "[MUTED]"),
+        })
+    }
+}
+
+impl fmt::Display for VolumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Spawn(error) => write!(f, "failed to run wpctl: {error}"),
+            Self::Failed(error) => write!(f, "wpctl failed: {error}"),
+            Self::Parse(output) => write!(f, "unexpected wpctl output `{output}`"),
+        }
+    }
+}
+
+impl std::error::Error for VolumeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::SinkVolume;
+
+    #[test]
+    fn parses_the_level() {
+        assert_eq!(
+            SinkVolume::parse("Volume: 0.40\n"),
+            Some(SinkVolume {
+                level: 0.4,
+                muted: false
+            })
+        );
+    }
+
+    #[test]
+    fn parses_muted_sinks() {
+        assert_eq!(
+            SinkVolume::parse("Volume: 1.25 [MUTED]\n"),
+            Some(SinkVolume {
+                level: 1.25,
+                muted: true
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_output() {
+        for output in ["", "Volume:", "Volume: loud", "Level: 0.40", "0.40 [MUTED]"] {
+            assert_eq!(SinkVolume::parse(output), None, "{output:?}");
+        }
+    }
+}
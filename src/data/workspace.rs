@@ -1,5 +1,5 @@
 use hyprland::{
-    data::{Workspace as HyprlandWorkspace, Workspaces},
+    data::{Monitors, Workspace as HyprlandWorkspace, Workspaces},
     shared::{HyprData, HyprDataActive, HyprDataVec},
 };
 
@@ -20,21 +20,37 @@ pub struct Workspace {
 }
 
 impl Workspace {
+    // With a monitor only the workspaces on it and the empty ones are listed,
+    // and the active workspace is the one shown on that monitor.
     #[must_use]
-    pub fn all() -> Vec<Self> {
+    pub fn all(count: usize, monitor: Option<&str>) -> Vec<Self> {
         let workspaces = Workspaces::get()
             .map(HyprDataVec::to_vec)
             .unwrap_or_default();
-        let active_workspace = HyprlandWorkspace::get_active().ok();
+        let active_workspace = match monitor {
+            Some(name) => Monitors::get()
+                .map(HyprDataVec::to_vec)
+                .unwrap_or_default()
+                .into_iter()
+                .find(|monitor| monitor.name == name)
+                .map(|monitor| monitor.active_workspace.id),
+            None => HyprlandWorkspace::get_active()
+                .ok()
+                .map(|workspace| workspace.id),
+        };
 
-        let mut all = (1..=10)
+        let mut all = (1..=count)
+            .filter(|id| {
+                !workspaces.iter().any(|workspace| {
+                    workspace.id == *id as i32
+                        && monitor.is_some_and(|name| workspace.monitor != name)
+                })
+            })
             .map(|id| Self {
                 id,
                 position: WorkspacePosition::Between,
                 exists: workspaces.iter().any(|workspace| workspace.id == id as i32),
-                active: active_workspace
-                    .as_ref()
-                    .is_some_and(|workspace| workspace.id == id as i32),
+                active: active_workspace == Some(id as i32),
             })
             .collect::<Vec<_>>();
 
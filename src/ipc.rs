@@ -0,0 +1,202 @@
+use std::{fmt, io, path::PathBuf, str::FromStr, sync::Arc};
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{UnixListener, UnixStream},
+    sync::oneshot,
+};
+use tracing::debug;
+
+use crate::{config::Color, utils::ColorExt};
+
+pub const COMMANDS: &str = "toggle-app-search, reload, theme <COLOR>, show, hide, toggle, modules";
+
+pub type Response = Result<String, String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    ToggleAppSearch,
+    Reload,
+    Theme(Color),
+    Show,
+    Hide,
+    Toggle,
+    Modules,
+}
+
+// A request received by the running instance, it has to be answered with
+// `reply` or the client gets an error.
+#[derive(Debug)]
+pub struct Call {
+    pub request: Request,
+    reply: oneshot::Sender<Response>,
+}
+
+// Removes the socket once the listener stops, including when its task is
+// dropped as the bar exits.
+struct Socket(PathBuf);
+
+#[derive(Debug)]
+pub enum IpcError {
+    Connect(PathBuf, io::Error),
+    Io(io::Error),
+    Failed(String),
+}
+
+impl Call {
+    pub fn reply(self, response: Response) {
+        let _ = self.reply.send(response);
+    }
+}
+
+#[must_use]
+pub fn socket_path() -> PathBuf {
+    glib::user_runtime_dir().join("barry.sock")
+}
+
+// The protocol is a single command line from the client, answered with `ok` or
+// `error` on the first line and the response body after it.
+pub async fn listen<F>(handle: F) -> io::Result<()>
+where
+    F: Fn(Call) + Send + Sync + 'static,
+{
+    let path = socket_path();
+
+    if UnixStream::connect(&path).await.is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AddrInUse,
+            format!("another instance is listening on {}", path.display()),
+        ));
+    }
+
+    // Whatever is left at the path is a stale socket from an instance that
+    // didn't exit cleanly.
+    let _ = std::fs::remove_file(&path);
+
+    let listener = UnixListener::bind(&path)?;
+    let _socket = Socket(path);
+    let handle = Arc::new(handle);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let handle = handle.clone();
+
+        tokio::spawn(async move {
+            if let Err(error) = respond(stream, handle.as_ref()).await {
+                debug!(%error, "failed to answer a command");
+            }
+        });
+    }
+}
+
+async fn respond<F: Fn(Call)>(stream: UnixStream, handle: &F) -> io::Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut line = String::new();
+
+    BufReader::new(reader).read_line(&mut line).await?;
+
+    let response = match line.parse() {
+        Ok(request) => {
+            let (reply, response) = oneshot::channel();
+
+            handle(Call { request, reply });
+
+            response
+                .await
+                .unwrap_or_else(|_| Err("the request was dropped without an answer".into()))
+        }
+        Err(error) => Err(error),
+    };
+
+    let message = match response {
+        Ok(body) => format!("ok\n{body}"),
+        Err(error) => format!("error\n{error}"),
+    };
+
+    writer.write_all(message.as_bytes()).await?;
+    writer.shutdown().await
+}
+
+pub async fn send(request: &Request) -> Result<String, IpcError> {
+    let path = socket_path();
+    let mut stream = UnixStream::connect(&path)
+        .await
+        .map_err(|error| IpcError::Connect(path, error))?;
+
+    stream.write_all(format!("{request}\n").as_bytes()).await?;
+
+    let mut message = String::new();
+
+    stream.read_to_string(&mut message).await?;
+
+    match message.split_once('\n') {
+        Some(("ok", body)) => Ok(body.into()),
+        Some(("error", error)) => Err(IpcError::Failed(error.into())),
+        _ => Err(IpcError::Failed(format!("unexpected response `{message}`"))),
+    }
+}
+
+impl FromStr for Request {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let words = line.split_whitespace().collect::<Vec<_>>();
+
+        match words.as_slice() {
+            ["toggle-app-search"] => Ok(Self::ToggleAppSearch),
+            ["reload"] => Ok(Self::Reload),
+            ["theme", color] => color.parse().map(Self::Theme),
+            ["show"] => Ok(Self::Show),
+            ["hide"] => Ok(Self::Hide),
+            ["toggle"] => Ok(Self::Toggle),
+            ["modules"] => Ok(Self::Modules),
+            [] => Err(format!("missing command, expected one of {COMMANDS}")),
+            _ => Err(format!(
+                "invalid command `{}`, expected one of {COMMANDS}",
+                line.trim()
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ToggleAppSearch => write!(f, "toggle-app-search"),
+            Self::Reload => write!(f, "reload"),
+            Self::Theme(color) => write!(f, "theme {}", color.0.to_hex()),
+            Self::Show => write!(f, "show"),
+            Self::Hide => write!(f, "hide"),
+            Self::Toggle => write!(f, "toggle"),
+            Self::Modules => write!(f, "modules"),
+        }
+    }
+}
+
+impl Drop for Socket {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+impl From<io::Error> for IpcError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl fmt::Display for IpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Connect(path, error) => write!(
+                f,
+                "failed to connect to {}, is the bar running? ({error})",
+                path.display()
+            ),
+            Self::Io(error) => write!(f, "failed to talk to the bar: {error}"),
+            Self::Failed(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for IpcError {}
@@ -0,0 +1,28 @@
+#![warn(clippy::all, clippy::pedantic, clippy::nursery, clippy::cargo)]
+#![allow(
+    clippy::cargo_common_metadata,
+    clippy::too_many_lines,
+    clippy::module_name_repetitions,
+    clippy::missing_panics_doc,
+    clippy::missing_errors_doc,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::multiple_crate_versions,
+    clippy::unreadable_literal
+)]
+
+pub mod apps;
+pub mod components;
+pub mod config;
+pub mod data;
+pub mod ipc;
+pub mod logging;
+pub mod mpris;
+pub mod styling;
+pub mod theme;
+pub mod utils;
+pub mod widgets;
+
+pub use theme::theme;
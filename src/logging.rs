@@ -0,0 +1,64 @@
+use std::{
+    env, fmt,
+    fs::OpenOptions,
+    io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use tracing_subscriber::{filter::ParseError, EnvFilter};
+
+pub const ENV_VAR: &str = "BARRY_LOG";
+
+const DEFAULT_FILTER: &str = "info";
+
+#[derive(Debug)]
+pub enum LoggingError {
+    Filter(String, ParseError),
+    File(PathBuf, io::Error),
+}
+
+// `--log-level` wins over `BARRY_LOG`, both take a level like `debug` or
+// per-module directives like `barry::mpris=trace,zbus=warn`.
+pub fn init(level: Option<&str>, file: Option<&Path>) -> Result<(), LoggingError> {
+    let directives = level
+        .map(String::from)
+        .or_else(|| env::var(ENV_VAR).ok())
+        .unwrap_or_else(|| DEFAULT_FILTER.into());
+    let filter =
+        EnvFilter::try_new(&directives).map_err(|error| LoggingError::Filter(directives, error))?;
+    let subscriber = tracing_subscriber::fmt().with_env_filter(filter);
+
+    match file {
+        Some(path) => {
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|error| LoggingError::File(path.into(), error))?;
+
+            subscriber
+                .with_ansi(false)
+                .with_writer(Mutex::new(file))
+                .init();
+        }
+        None => subscriber.with_writer(io::stderr).init(),
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for LoggingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Filter(directives, error) => {
+                write!(f, "invalid log level `{directives}`: {error}")
+            }
+            Self::File(path, error) => {
+                write!(f, "failed to open the log file {}: {error}", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoggingError {}
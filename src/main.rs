@@ -4,6 +4,7 @@
     clippy::too_many_lines,
     clippy::module_name_repetitions,
     clippy::missing_panics_doc,
+    clippy::missing_errors_doc,
     clippy::cast_precision_loss,
     clippy::cast_sign_loss,
     clippy::cast_possible_truncation,
@@ -12,78 +13,260 @@
     clippy::unreadable_literal
 )]
 
-pub mod apps;
-pub mod components;
-pub mod data;
-pub mod mpris;
-pub mod styling;
-pub mod utils;
-pub mod widgets;
+use barry::{
+    apps::{
+        app_search::{self, AppSearch},
+        bars::{Bars, BarsInit},
+    },
+    config::{Color, Config, ThemeOverrides},
+    ipc::{self, Request, COMMANDS},
+    logging,
+    styling::{reset_styles, user::UserStyleSheet},
+    theme::{self, export, roles, Contrast, Mode, SchemeVariant},
+    utils::ColorExt,
+};
+use clap::{Args, Parser, Subcommand};
+use relm4::RelmApp;
+use serde::{
+    de::{value, DeserializeOwned, IntoDeserializer},
+    Deserialize,
+};
+use std::{fmt::Display, path::PathBuf, process};
 
-use apps::{app_search::AppSearch, bar::Bar};
-use material_colors::{color::Argb, scheme::Scheme, theme::ThemeBuilder};
-use relm4::{once_cell::sync::OnceCell, RelmApp};
-use std::{env, str::FromStr};
+#[derive(Parser)]
+#[command(version, about = "A Material You status bar and launcher for Hyprland")]
+struct Cli {
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Read the configuration from PATH instead of the default location"
+    )]
+    config: Option<PathBuf>,
 
-static THEME: OnceCell<Scheme> = OnceCell::new();
+    #[arg(
+        long,
+        global = true,
+        value_name = "LEVEL",
+        help = "error, warn, info, debug or trace, overrides BARRY_LOG"
+    )]
+    log_level: Option<String>,
 
-pub fn theme() -> &'static Scheme {
-    THEME.get().unwrap()
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Append the log to PATH instead of printing it"
+    )]
+    log_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    theme: ThemeArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Args)]
+struct ThemeArgs {
+    #[arg(
+        long = "theme",
+        global = true,
+        value_name = "COLOR",
+        help = "Generate the theme from a hex source color"
+    )]
+    source: Option<Color>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Generate the theme from an image and follow its changes"
+    )]
+    wallpaper: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_parser = parse_value::<Mode>,
+        help = "light, dark or system"
+    )]
+    mode: Option<Mode>,
+
+    #[arg(
+        long,
+        global = true,
+        value_parser = parse_value::<SchemeVariant>,
+        help = "Material scheme variant, like tonal-spot, vibrant or content"
+    )]
+    variant: Option<SchemeVariant>,
+
+    #[arg(
+        long,
+        global = true,
+        allow_negative_numbers = true,
+        value_parser = parse_contrast,
+        help = "Contrast level between -1.0 and 1.0"
+    )]
+    contrast: Option<Contrast>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Show a bar on every monitor")]
+    Bar,
+    #[command(about = "Open the application launcher")]
+    AppSearch,
+    #[command(about = "Print the colors of the generated scheme")]
+    Theme {
+        #[command(subcommand)]
+        command: Option<ThemeCommand>,
+    },
+    #[command(about = "Send a command to the running bar")]
+    Msg {
+        #[arg(required = true, num_args = 1.., help = COMMANDS)]
+        command: Vec<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum ThemeCommand {
+    #[command(about = "Render a template with the colors of the generated scheme")]
+    Export {
+        #[arg(help = "A file with placeholders like {{primary}} or {{on_surface.rgba}}")]
+        template: PathBuf,
+
+        #[arg(
+            long,
+            short,
+            value_name = "PATH",
+            help = "Write to PATH instead of printing the result"
+        )]
+        output: Option<PathBuf>,
+    },
 }
 
 #[tokio::main(flavor = "current_thread")]
 async fn main() {
-    let mut args = env::args().skip(1).collect::<Vec<_>>();
-
-    args.iter()
-        .position(|arg| arg == "--theme")
-        .map_or_else(
-            || {
-                THEME.set(
-                    ThemeBuilder::with_source(Argb::from_u32(0x2C563E))
-                        .build()
-                        .schemes
-                        .dark,
-                )
-            },
-            |position| {
-                args.remove(position);
-
-                let color = args.remove(position);
-
-                THEME.set(
-                    ThemeBuilder::with_source(Argb::from_str(&color).unwrap())
-                        .build()
-                        .schemes
-                        .dark,
-                )
-            },
-        )
-        .unwrap();
-
-    let app_name = args.first().map_or_else(
-        || panic!("app name requiered"),
-        |app| format!("kz.aiving.{app}"),
-    );
-
-    let app = RelmApp::new(&app_name).with_args(args);
-
-    let display = gdk::Display::default().unwrap();
-    let provider = gtk::CssProvider::new();
-
-    provider.load_from_string("* { all: unset; }");
-
-    gtk::style_context_add_provider_for_display(
-        &display,
-        &provider,
-        gtk::STYLE_PROVIDER_PRIORITY_APPLICATION + 200,
-    );
-
-    if app_name == "kz.aiving.bar" {
-        app.run::<Bar>(());
-    } else if app_name == "kz.aiving.app-search" {
-        app.run::<AppSearch>(());
-    } else {
-        panic!("there is no app called {app_name}");
+    let cli = Cli::parse();
+
+    logging::init(cli.log_level.as_deref(), cli.log_file.as_deref())
+        .unwrap_or_else(|error| fail(error));
+
+    // Messages go to the running instance, which has its own configuration.
+    if let Command::Msg { command } = &cli.command {
+        let request = command
+            .join(" ")
+            .parse::<Request>()
+            .unwrap_or_else(|error| fail(error));
+
+        match ipc::send(&request).await {
+            Ok(body) => print!("{body}"),
+            Err(error) => fail(error),
+        }
+
+        return;
     }
+
+    let config = cli
+        .config
+        .as_deref()
+        .map_or_else(Config::load, Config::load_from)
+        .unwrap_or_else(|error| fail(error));
+
+    let overrides = ThemeOverrides::from(cli.theme);
+    let mut theme_config = config.theme.clone();
+
+    overrides.apply(&mut theme_config);
+
+    theme::configure(&theme_config);
+
+    match cli.command {
+        Command::Bar => {
+            let app = RelmApp::new("kz.aiving.bar").with_args(vec![]);
+            let user_stylesheet = load_styles();
+
+            theme::follow_portal();
+
+            app.visible_on_activate(false).run::<Bars>(BarsInit {
+                config,
+                overrides,
+                user_stylesheet,
+            });
+        }
+        Command::AppSearch => {
+            let app = RelmApp::new(app_search::APP_ID).with_args(vec![]);
+
+            if app_search::toggle_running() {
+                return;
+            }
+
+            let _user_stylesheet = load_styles();
+
+            theme::follow_portal();
+
+            app.run::<AppSearch>(());
+        }
+        Command::Theme { command: None } => {
+            theme::read_portal().await;
+
+            for (role, color) in roles(&theme::theme()) {
+                println!("{role}: {}", color.to_hex());
+            }
+        }
+        Command::Theme {
+            command: Some(ThemeCommand::Export { template, output }),
+        } => {
+            theme::read_portal().await;
+
+            let rendered = export::render_file(&template).unwrap_or_else(|error| fail(error));
+
+            match output {
+                Some(output) => {
+                    export::write(&output, &rendered).unwrap_or_else(|error| fail(error))
+                }
+                None => print!("{rendered}"),
+            }
+        }
+        Command::Msg { .. } => unreachable!(),
+    }
+}
+
+impl From<ThemeArgs> for ThemeOverrides {
+    fn from(args: ThemeArgs) -> Self {
+        Self {
+            source: args.source,
+            wallpaper: args.wallpaper,
+            mode: args.mode,
+            variant: args.variant,
+            contrast: args.contrast,
+        }
+    }
+}
+
+fn load_styles() -> UserStyleSheet {
+    let display = gdk::Display::default()
+        .unwrap_or_else(|| fail("failed to connect to a display, is a Wayland session running?"));
+
+    reset_styles(&display);
+
+    UserStyleSheet::load(&display, &UserStyleSheet::path())
+}
+
+fn parse_value<T: DeserializeOwned>(input: &str) -> Result<T, String> {
+    T::deserialize(input.into_deserializer()).map_err(|error: value::Error| error.to_string())
+}
+
+fn parse_contrast(input: &str) -> Result<Contrast, String> {
+    let level = input
+        .parse::<f64>()
+        .map_err(|_| format!("invalid contrast `{input}`, expected a number"))?;
+
+    Contrast::new(level).map_err(|error| error.to_string())
+}
+
+fn fail(error: impl Display) -> ! {
+    eprintln!("error: {error}");
+
+    process::exit(1);
 }
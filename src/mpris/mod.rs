@@ -3,6 +3,7 @@ use generated::{
     player::{Metadata, PlaybackStatus, PlayerProxy},
 };
 use tokio_stream::{Stream, StreamExt};
+use tracing::debug;
 use zbus::{
     fdo::NameOwnerChanged,
     proxy::{CacheProperties, PropertyStream},
@@ -17,16 +18,15 @@ pub struct Players<'a> {
 }
 
 impl<'a> Players<'a> {
-    pub async fn new() -> Self {
-        let connection = Connection::session().await.unwrap();
+    pub async fn new() -> zbus::Result<Self> {
+        let connection = Connection::session().await?;
 
         let proxy = zbus::fdo::DBusProxy::builder(&connection)
             .cache_properties(CacheProperties::No)
             .build()
-            .await
-            .unwrap();
+            .await?;
 
-        Self { proxy }
+        Ok(Self { proxy })
     }
 
     pub async fn owner_changed_steam(&self) -> Option<impl Stream<Item = NameOwnerChanged> + '_> {
@@ -61,9 +61,11 @@ pub struct Player<'a> {
 
 impl<'a> Player<'a> {
     pub async fn all() -> Vec<Self> {
-        let connection = Connection::session().await.unwrap();
+        let Ok(connection) = Connection::session().await else {
+            return vec![];
+        };
 
-        let message = connection
+        let names: Vec<String> = match connection
             .call_method(
                 Some("org.freedesktop.DBus"),
                 "/",
@@ -72,9 +74,11 @@ impl<'a> Player<'a> {
                 &(),
             )
             .await
-            .unwrap();
+        {
+            Ok(message) => message.body().deserialize().unwrap_or_default(),
+            Err(_) => return vec![],
+        };
 
-        let names: Vec<String> = message.body().deserialize().unwrap();
         let mut names = names
             .into_iter()
             .filter(|name| name.starts_with("org.mpris.MediaPlayer2."))
@@ -127,6 +131,8 @@ impl<'a> Player<'a> {
     pub async fn find_active() -> Option<Self> {
         let players = Self::all().await;
 
+        debug!(players = players.len(), "looking for an active player");
+
         if players.is_empty() {
             return None;
         }
@@ -154,6 +160,11 @@ impl<'a> Player<'a> {
         first_paused.or(first_with_track).or(first_found)
     }
 
+    #[must_use]
+    pub fn name(&self) -> &str {
+        self.proxy.destination().as_str()
+    }
+
     pub async fn get_metadata(&self) -> Option<Metadata> {
         self.player_proxy.metadata().await.ok()
     }
@@ -178,19 +189,17 @@ impl<'a> Player<'a> {
         self.player_proxy.position().await.ok()
     }
 
-    pub async fn props_changed_steam(&self) -> MessageStream {
+    pub async fn props_changed_steam(&self) -> zbus::Result<MessageStream> {
         MessageStream::for_match_rule(
             MatchRule::builder()
                 .msg_type(zbus::message::Type::Signal)
                 .interface("org.freedesktop.DBus.Properties")
                 .and_then(|x| x.member("PropertiesChanged"))
-                .and_then(|x| x.path("/org/mpris/MediaPlayer2"))
-                .unwrap()
+                .and_then(|x| x.path("/org/mpris/MediaPlayer2"))?
                 .build(),
             self.proxy.connection(),
             None,
         )
         .await
-        .unwrap()
     }
 }
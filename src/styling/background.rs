@@ -0,0 +1,215 @@
+use std::fmt;
+
+use material_colors::color::Argb;
+
+use crate::utils::ColorExt;
+
+use super::length::Length;
+
+// One layer of `background-image`, the first layer is drawn on top.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Image {
+    File(String),
+    Gradient(Gradient),
+}
+
+// Gradients are built from their first two stops, CSS needs at least two.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    kind: GradientKind,
+    stops: Vec<Stop>,
+}
+
+// A stop without a position is spread evenly between its neighbours.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stop {
+    color: Argb,
+    position: Option<Length>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    Linear(Direction),
+    Radial(RadialShape),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    // Clockwise from the top.
+    Angle(f64),
+    ToTop,
+    ToRight,
+    ToBottom,
+    ToLeft,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RadialShape {
+    Circle,
+    #[default]
+    Ellipse,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Color,
+    Hue,
+    Saturation,
+    Luminosity,
+}
+
+impl Gradient {
+    #[must_use]
+    pub fn linear(direction: Direction, from: impl Into<Stop>, to: impl Into<Stop>) -> Self {
+        Self {
+            kind: GradientKind::Linear(direction),
+            stops: vec![from.into(), to.into()],
+        }
+    }
+
+    #[must_use]
+    pub fn radial(shape: RadialShape, from: impl Into<Stop>, to: impl Into<Stop>) -> Self {
+        Self {
+            kind: GradientKind::Radial(shape),
+            stops: vec![from.into(), to.into()],
+        }
+    }
+
+    #[must_use]
+    pub fn stop(mut self, stop: impl Into<Stop>) -> Self {
+        self.stops.push(stop.into());
+
+        self
+    }
+}
+
+impl Stop {
+    #[must_use]
+    pub const fn at(color: Argb, position: Length) -> Self {
+        Self {
+            color,
+            position: Some(position),
+        }
+    }
+}
+
+impl From<Argb> for Stop {
+    fn from(color: Argb) -> Self {
+        Self {
+            color,
+            position: None,
+        }
+    }
+}
+
+impl From<Gradient> for Image {
+    fn from(gradient: Gradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
+
+impl fmt::Display for Image {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::File(path) => {
+                f.write_str("url(\"file://")?;
+
+                // The path is written as a CSS string, quotes and backslashes
+                // in it would end the string or start an escape.
+                for character in path.chars() {
+                    match character {
+                        '"' | '\\' => write!(f, "\\{character}")?,
+                        '\n' => f.write_str("\\a ")?,
+                        character => write!(f, "{character}")?,
+                    }
+                }
+
+                f.write_str("\")")
+            }
+            Self::Gradient(gradient) => write!(f, "{gradient}"),
+        }
+    }
+}
+
+impl fmt::Display for Gradient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            GradientKind::Linear(direction) => write!(f, "linear-gradient({direction}")?,
+            GradientKind::Radial(shape) => write!(f, "radial-gradient({shape}")?,
+        }
+
+        for stop in &self.stops {
+            write!(f, ", {stop}")?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+impl fmt::Display for Stop {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.color.to_rgba())?;
+
+        match self.position {
+            Some(position) => write!(f, " {position}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Angle(degrees) => write!(f, "{degrees}deg"),
+            Self::ToTop => f.write_str("to top"),
+            Self::ToRight => f.write_str("to right"),
+            Self::ToBottom => f.write_str("to bottom"),
+            Self::ToLeft => f.write_str("to left"),
+        }
+    }
+}
+
+impl fmt::Display for RadialShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Circle => f.write_str("circle"),
+            Self::Ellipse => f.write_str("ellipse"),
+        }
+    }
+}
+
+impl fmt::Display for BlendMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Normal => "normal",
+            Self::Multiply => "multiply",
+            Self::Screen => "screen",
+            Self::Overlay => "overlay",
+            Self::Darken => "darken",
+            Self::Lighten => "lighten",
+            Self::ColorDodge => "color-dodge",
+            Self::ColorBurn => "color-burn",
+            Self::HardLight => "hard-light",
+            Self::SoftLight => "soft-light",
+            Self::Difference => "difference",
+            Self::Exclusion => "exclusion",
+            Self::Color => "color",
+            Self::Hue => "hue",
+            Self::Saturation => "saturation",
+            Self::Luminosity => "luminosity",
+        })
+    }
+}
@@ -1,19 +1,25 @@
 use std::fmt;
 
 use material_colors::color::Argb;
+use tracing::warn;
 
 use crate::utils::ColorExt;
 
+use super::length::Length;
+
+// `Custom` follows the CSS order: top left, top right, bottom right and bottom
+// left.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BorderRadius {
-    All(u32),
-    Custom(u32, u32, u32, u32),
-    TopLeft(u32),
-    TopRight(u32),
-    BottomLeft(u32),
-    BottomRight(u32),
+    All(Length),
+    Custom(Length, Length, Length, Length),
+    TopLeft(Length),
+    TopRight(Length),
+    BottomLeft(Length),
+    BottomRight(Length),
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum BorderStyle {
     #[default]
     None,
@@ -28,17 +34,24 @@ pub enum BorderStyle {
     Ridge,
 }
 
-#[derive(Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Border {
-    thickness: u32,
+    thickness: Length,
     style: BorderStyle,
     color: Argb,
 }
 
 impl Border {
+    // A negative thickness is clamped to zero, GTK doesn't take percentages
+    // for it so those leave the thickness as it was.
     #[must_use]
-    pub const fn thickness(mut self, thickness: u32) -> Self {
-        self.thickness = thickness;
+    pub fn thickness(mut self, thickness: impl Into<Length>) -> Self {
+        let thickness = thickness.into();
+
+        match thickness.absolute() {
+            Some(thickness) => self.thickness = thickness.non_negative(),
+            None => warn!(%thickness, "percentages are not supported for borders, leaving it out"),
+        }
 
         self
     }
@@ -58,22 +71,23 @@ impl Border {
     }
 }
 
-impl fmt::Display for BorderRadius {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::All(radius) => write!(f, "border-radius: {radius}px;"),
-            Self::Custom(top_left, top_right, bottom_left, bottom_right) => {
-                write!(
-                    f,
-                    "border-radius: {top_left}px {top_right}px {bottom_left}px {bottom_right}px;"
-                )
-            }
-            Self::TopLeft(radius) => write!(f, "border-top-left-radius: {radius}px;"),
-            Self::TopRight(radius) => write!(f, "border-top-right-radius: {radius}px;"),
-            Self::BottomLeft(radius) => write!(f, "border-bottom-left-radius: {radius}px;"),
-            Self::BottomRight(radius) => {
-                write!(f, "border-bottom-right-radius: {radius}px;")
-            }
+impl BorderRadius {
+    // Top left, top right, bottom right and bottom left, `None` for the corners
+    // it leaves alone.
+    #[must_use]
+    pub const fn corners(&self) -> [Option<Length>; 4] {
+        match *self {
+            Self::All(radius) => [Some(radius); 4],
+            Self::Custom(top_left, top_right, bottom_right, bottom_left) => [
+                Some(top_left),
+                Some(top_right),
+                Some(bottom_right),
+                Some(bottom_left),
+            ],
+            Self::TopLeft(radius) => [Some(radius), None, None, None],
+            Self::TopRight(radius) => [None, Some(radius), None, None],
+            Self::BottomRight(radius) => [None, None, Some(radius), None],
+            Self::BottomLeft(radius) => [None, None, None, Some(radius)],
         }
     }
 }
@@ -82,7 +96,7 @@ impl fmt::Display for Border {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{}px {} {}",
+            "{} {} {}",
             self.thickness,
             match self.style {
                 BorderStyle::None => "none",
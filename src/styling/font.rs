@@ -1,16 +1,19 @@
 use std::fmt;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FontWeight {
     Thin,
     Normal,
+    Medium,
     Bold,
 }
 
 impl fmt::Display for FontWeight {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Thin => f.write_str("thin"),
+            Self::Thin => f.write_str("100"),
             Self::Normal => f.write_str("normal"),
+            Self::Medium => f.write_str("500"),
             Self::Bold => f.write_str("bold"),
         }
     }
@@ -0,0 +1,78 @@
+use std::fmt;
+
+// `Em` is relative to the font size of the widget, `Rem` to the one of the
+// window, so sizes in either follow the font on HiDPI setups.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    Px(f64),
+    Em(f64),
+    Rem(f64),
+    Percent(f64),
+}
+
+impl Length {
+    pub const ZERO: Self = Self::Px(0.0);
+
+    #[must_use]
+    pub const fn value(self) -> f64 {
+        match self {
+            Self::Px(value) | Self::Em(value) | Self::Rem(value) | Self::Percent(value) => value,
+        }
+    }
+
+    #[must_use]
+    pub fn is_negative(self) -> bool {
+        self.value() < 0.0
+    }
+
+    // GTK only takes percentages for radii and font sizes, anywhere else it
+    // drops the whole declaration. `None` for a percentage, which callers
+    // leave out with a warning instead.
+    #[must_use]
+    pub const fn absolute(self) -> Option<Self> {
+        match self {
+            Self::Percent(_) => None,
+            length => Some(length),
+        }
+    }
+
+    // Most properties reject negative lengths, GTK would drop the whole
+    // declaration, so they are clamped to zero instead.
+    #[must_use]
+    pub fn non_negative(self) -> Self {
+        if self.is_negative() {
+            Self::ZERO
+        } else {
+            self
+        }
+    }
+}
+
+impl Default for Length {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl From<i32> for Length {
+    fn from(value: i32) -> Self {
+        px(value)
+    }
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Px(value) => write!(f, "{value}px"),
+            Self::Em(value) => write!(f, "{value}em"),
+            Self::Rem(value) => write!(f, "{value}rem"),
+            Self::Percent(value) => write!(f, "{value}%"),
+        }
+    }
+}
+
+// Shorthand for whole pixels, `Thickness::All(px(4))`.
+#[must_use]
+pub fn px(value: i32) -> Length {
+    Length::Px(f64::from(value))
+}
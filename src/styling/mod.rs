@@ -1,13 +1,36 @@
+use std::{cell::RefCell, fmt, rc::Rc};
+
 use gtk::prelude::*;
 use style::Style;
 use stylesheet::StyleSheet;
+use tracing::warn;
 
+pub mod background;
 #[allow(deprecated)]
 pub mod border;
 pub mod font;
+pub mod length;
+pub mod preset;
+pub mod property;
+pub mod selector;
 pub mod style;
 pub mod stylesheet;
 pub mod thickness;
+pub mod token;
+pub mod transition;
+pub mod user;
+
+pub const RESET_PRIORITY: u32 = gtk::STYLE_PROVIDER_PRIORITY_APPLICATION + 200;
+pub const STYLE_PRIORITY: u32 = 900;
+pub const STYLESHEET_PRIORITY: u32 = 1000;
+pub const USER_PRIORITY: u32 = gtk::STYLE_PROVIDER_PRIORITY_USER + 300;
+
+// A declaration GTK failed to parse, as it appears in the generated CSS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub declaration: String,
+    pub message: String,
+}
 
 pub trait StyleExt {
     fn set_style(&self, style: Style);
@@ -16,24 +39,24 @@ pub trait StyleExt {
     fn set_child_stylesheet(&self, child: gtk::Widget, stylesheet: StyleSheet);
 }
 
+// Keys of the providers attached to a widget, restyling reloads them in place
+// instead of adding a new provider on every call.
+const STYLE_PROVIDER: &str = "barry-style-provider";
+const STYLESHEET_PROVIDER: &str = "barry-stylesheet-provider";
+
 impl<T: WidgetExt> StyleExt for T {
     fn set_style(&self, style: Style) {
-        let provider = gtk::CssProvider::new();
+        let provider = provider(self, STYLE_PROVIDER, STYLE_PRIORITY);
+        let css = style.with_class_name(self.css_name().as_str());
 
-        provider.load_from_string(&style.with_class_name(self.css_name().as_str()));
-
-        #[allow(deprecated)]
-        self.style_context().add_provider(&provider, 900);
+        report(self, &load(&provider, &css));
     }
 
     fn set_stylesheet(&self, stylesheet: StyleSheet) {
-        let provider = gtk::CssProvider::new();
-        let data = stylesheet.with_class_name(self.css_name().as_str());
-
-        provider.load_from_string(&data);
+        let provider = provider(self, STYLESHEET_PROVIDER, STYLESHEET_PRIORITY);
+        let css = stylesheet.with_class_name(self.css_name().as_str());
 
-        #[allow(deprecated)]
-        self.style_context().add_provider(&provider, 1000);
+        report(self, &load(&provider, &css));
     }
 
     fn set_child_style(&self, child: gtk::Widget, style: Style) {
@@ -44,3 +67,86 @@ impl<T: WidgetExt> StyleExt for T {
         child.set_stylesheet(stylesheet);
     }
 }
+
+fn provider(widget: &impl WidgetExt, key: &str, priority: u32) -> gtk::CssProvider {
+    // SAFETY: the keys are private to this module and only ever hold a
+    // `gtk::CssProvider`.
+    if let Some(provider) = unsafe { widget.data::<gtk::CssProvider>(key) } {
+        return unsafe { provider.as_ref() }.clone();
+    }
+
+    let provider = gtk::CssProvider::new();
+
+    #[allow(deprecated)]
+    widget.style_context().add_provider(&provider, priority);
+
+    unsafe { widget.set_data(key, provider.clone()) };
+
+    provider
+}
+
+// Parses `css` on its own, without applying it anywhere.
+#[must_use]
+pub fn parse_errors(css: &str) -> Vec<ParseError> {
+    load(&gtk::CssProvider::new(), css)
+}
+
+fn load(provider: &gtk::CssProvider, css: &str) -> Vec<ParseError> {
+    let errors = Rc::new(RefCell::new(vec![]));
+
+    let handler = provider.connect_parsing_error({
+        let errors = errors.clone();
+        let css = css.to_owned();
+
+        move |_, section, error| {
+            errors.borrow_mut().push(ParseError {
+                declaration: declaration(
+                    &css,
+                    section.start_location().bytes(),
+                    section.end_location().bytes(),
+                )
+                .into(),
+                message: error.to_string(),
+            });
+        }
+    });
+
+    provider.load_from_string(css);
+    provider.disconnect(handler);
+
+    errors.take()
+}
+
+fn report(widget: &impl WidgetExt, errors: &[ParseError]) {
+    for error in errors {
+        warn!(widget = %widget.type_().name(), %error, "invalid generated CSS");
+    }
+}
+
+// GTK points at the offending token, widened here to the whole declaration.
+fn declaration(css: &str, start: usize, end: usize) -> &str {
+    let (Some(before), Some(after)) = (css.get(..start), css.get(end..)) else {
+        return "";
+    };
+
+    let from = before.rfind(['{', ';']).map_or(0, |index| index + 1);
+    let to = after
+        .find([';', '}'])
+        .map_or(css.len(), |index| end + index);
+
+    css[from..to].trim()
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.declaration, self.message)
+    }
+}
+
+pub fn reset_styles(display: &gdk::Display) {
+    let provider = gtk::CssProvider::new();
+
+    provider.load_from_string("* { all: unset; }");
+
+    gtk::style_context_add_provider_for_display(display, &provider, RESET_PRIORITY);
+}
@@ -0,0 +1,32 @@
+use material_colors::scheme::Scheme;
+
+use super::{length::px, style::Style, thickness::Thickness, token::Shape};
+
+// Shared looks for bar modules, components merge their own properties on top.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Preset {
+    // A fully rounded container, like the volume slider.
+    Pill,
+    // A raised surface for popovers and launchers.
+    Card,
+    // A small rounded container for a single value.
+    Chip,
+}
+
+impl Preset {
+    #[must_use]
+    pub fn style(self, theme: &Scheme) -> Style {
+        match self {
+            Self::Pill => Style::new()
+                .background_color(theme.surface_container_highest)
+                .shape(Shape::Full),
+            Self::Card => Style::new()
+                .background_color(theme.surface_container)
+                .shape(Shape::Medium)
+                .padding(&Thickness::All(px(4))),
+            Self::Chip => Style::new()
+                .background_color(theme.surface_container_highest)
+                .shape(Shape::Medium),
+        }
+    }
+}
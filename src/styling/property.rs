@@ -0,0 +1,151 @@
+use std::fmt;
+
+use material_colors::color::Argb;
+
+use crate::utils::ColorExt;
+
+use super::{
+    background::{BlendMode, Image},
+    border::Border,
+    font::FontWeight,
+    length::Length,
+    transition::Transition,
+};
+
+// Shorthands like `margin` or `border-radius` are stored as their longhands, so
+// setting a single side or corner later only overrides that one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Property {
+    BackgroundImage,
+    BackgroundSize,
+    BackgroundRepeat,
+    BackgroundPosition,
+    BackgroundBlendMode,
+    BackgroundColor,
+    Color,
+    Border,
+    BorderTopLeftRadius,
+    BorderTopRightRadius,
+    BorderBottomRightRadius,
+    BorderBottomLeftRadius,
+    BoxShadow,
+    FontFamily,
+    FontSize,
+    FontWeight,
+    MinWidth,
+    MinHeight,
+    MarginTop,
+    MarginRight,
+    MarginBottom,
+    MarginLeft,
+    PaddingTop,
+    PaddingRight,
+    PaddingBottom,
+    PaddingLeft,
+    Transition,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Color(Argb),
+    Length(Length),
+    Keyword(String),
+    Images(Vec<Image>),
+    BlendModes(Vec<BlendMode>),
+    Border(Border),
+    Shadow(Vec<Length>, Argb),
+    FontWeight(FontWeight),
+    Transitions(Vec<Transition>),
+}
+
+impl Property {
+    pub const MARGIN: [Self; 4] = [
+        Self::MarginTop,
+        Self::MarginRight,
+        Self::MarginBottom,
+        Self::MarginLeft,
+    ];
+    pub const PADDING: [Self; 4] = [
+        Self::PaddingTop,
+        Self::PaddingRight,
+        Self::PaddingBottom,
+        Self::PaddingLeft,
+    ];
+    pub const BORDER_RADIUS: [Self; 4] = [
+        Self::BorderTopLeftRadius,
+        Self::BorderTopRightRadius,
+        Self::BorderBottomRightRadius,
+        Self::BorderBottomLeftRadius,
+    ];
+
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::BackgroundImage => "background-image",
+            Self::BackgroundSize => "background-size",
+            Self::BackgroundRepeat => "background-repeat",
+            Self::BackgroundPosition => "background-position",
+            Self::BackgroundBlendMode => "background-blend-mode",
+            Self::BackgroundColor => "background-color",
+            Self::Color => "color",
+            Self::Border => "border",
+            Self::BorderTopLeftRadius => "border-top-left-radius",
+            Self::BorderTopRightRadius => "border-top-right-radius",
+            Self::BorderBottomRightRadius => "border-bottom-right-radius",
+            Self::BorderBottomLeftRadius => "border-bottom-left-radius",
+            Self::BoxShadow => "box-shadow",
+            Self::FontFamily => "font-family",
+            Self::FontSize => "font-size",
+            Self::FontWeight => "font-weight",
+            Self::MinWidth => "min-width",
+            Self::MinHeight => "min-height",
+            Self::MarginTop => "margin-top",
+            Self::MarginRight => "margin-right",
+            Self::MarginBottom => "margin-bottom",
+            Self::MarginLeft => "margin-left",
+            Self::PaddingTop => "padding-top",
+            Self::PaddingRight => "padding-right",
+            Self::PaddingBottom => "padding-bottom",
+            Self::PaddingLeft => "padding-left",
+            Self::Transition => "transition",
+        }
+    }
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Color(color) => f.write_str(&color.to_rgba()),
+            Self::Length(length) => write!(f, "{length}"),
+            Self::Keyword(keyword) => f.write_str(keyword),
+            Self::Images(images) => write_list(f, images),
+            Self::BlendModes(modes) => write_list(f, modes),
+            Self::Border(border) => write!(f, "{border}"),
+            Self::Shadow(lengths, color) => {
+                for length in lengths {
+                    write!(f, "{length} ")?;
+                }
+
+                f.write_str(&color.to_rgba())
+            }
+            Self::FontWeight(weight) => write!(f, "{weight}"),
+            Self::Transitions(transitions) => write_list(f, transitions),
+        }
+    }
+}
+
+fn write_list<T: fmt::Display>(f: &mut fmt::Formatter<'_>, items: &[T]) -> fmt::Result {
+    f.write_str(
+        &items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
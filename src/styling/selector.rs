@@ -0,0 +1,323 @@
+use std::fmt;
+
+// A selector relative to the styled widget, an empty one matches the widget
+// itself.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Selector {
+    parts: Vec<Part>,
+}
+
+// A selector without combinators like `button.flat:hover`, the only kind
+// `:not()` accepts. An empty one matches any widget. It can't hold another
+// `:not()`, GTK doesn't allow nesting them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Compound {
+    node: Option<Node>,
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Class(String),
+    PseudoClass(PseudoClass),
+    Not(Compound),
+    Node(Combinator, Node),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combinator {
+    Descendant,
+    Child,
+    NextSibling,
+    Sibling,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PseudoClass {
+    Hover,
+    Active,
+    Focus,
+    FocusWithin,
+    FocusVisible,
+    Checked,
+    Selected,
+    Disabled,
+    Backdrop,
+    FirstChild,
+    LastChild,
+    OnlyChild,
+    // `an+b`, `NthChild(2, 1)` matches the odd children.
+    NthChild(i32, i32),
+    NthLastChild(i32, i32),
+}
+
+// CSS node names of the GTK widgets used by the bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Node {
+    Any,
+    Box,
+    Button,
+    Label,
+    Image,
+    Entry,
+    Text,
+    Scale,
+    Trough,
+    Highlight,
+    Slider,
+    Progress,
+    ProgressBar,
+    Row,
+    ListView,
+    Popover,
+    Contents,
+    Arrow,
+    ScrolledWindow,
+    Scrollbar,
+    Undershoot,
+}
+
+impl Selector {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn class(mut self, class: &str) -> Self {
+        self.parts.push(Part::Class(class.into()));
+
+        self
+    }
+
+    #[must_use]
+    pub fn pseudo(mut self, pseudo_class: PseudoClass) -> Self {
+        self.parts.push(Part::PseudoClass(pseudo_class));
+
+        self
+    }
+
+    // `:not(...)`, matching widgets the compound selector doesn't.
+    #[must_use]
+    pub fn not(mut self, compound: Compound) -> Self {
+        self.parts.push(Part::Not(compound));
+
+        self
+    }
+
+    #[must_use]
+    pub fn hover(self) -> Self {
+        self.pseudo(PseudoClass::Hover)
+    }
+
+    #[must_use]
+    pub fn active(self) -> Self {
+        self.pseudo(PseudoClass::Active)
+    }
+
+    #[must_use]
+    pub fn focus(self) -> Self {
+        self.pseudo(PseudoClass::Focus)
+    }
+
+    #[must_use]
+    pub fn focus_within(self) -> Self {
+        self.pseudo(PseudoClass::FocusWithin)
+    }
+
+    #[must_use]
+    pub fn checked(self) -> Self {
+        self.pseudo(PseudoClass::Checked)
+    }
+
+    #[must_use]
+    pub fn disabled(self) -> Self {
+        self.pseudo(PseudoClass::Disabled)
+    }
+
+    #[must_use]
+    pub fn first_child(self) -> Self {
+        self.pseudo(PseudoClass::FirstChild)
+    }
+
+    #[must_use]
+    pub fn last_child(self) -> Self {
+        self.pseudo(PseudoClass::LastChild)
+    }
+
+    #[must_use]
+    pub fn nth_child(self, a: i32, b: i32) -> Self {
+        self.pseudo(PseudoClass::NthChild(a, b))
+    }
+
+    #[must_use]
+    pub fn combine(mut self, combinator: Combinator, node: Node) -> Self {
+        self.parts.push(Part::Node(combinator, node));
+
+        self
+    }
+
+    // `child(Node::Trough).child(Node::Highlight)` is `trough > highlight`.
+    #[must_use]
+    pub fn child(self, node: Node) -> Self {
+        self.combine(Combinator::Child, node)
+    }
+
+    #[must_use]
+    pub fn descendant(self, node: Node) -> Self {
+        self.combine(Combinator::Descendant, node)
+    }
+
+    #[must_use]
+    pub fn next_sibling(self, node: Node) -> Self {
+        self.combine(Combinator::NextSibling, node)
+    }
+
+    #[must_use]
+    pub fn sibling(self, node: Node) -> Self {
+        self.combine(Combinator::Sibling, node)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.parts.is_empty()
+    }
+}
+
+impl Compound {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub const fn node(mut self, node: Node) -> Self {
+        self.node = Some(node);
+
+        self
+    }
+
+    #[must_use]
+    pub fn class(mut self, class: &str) -> Self {
+        self.parts.push(Part::Class(class.into()));
+
+        self
+    }
+
+    #[must_use]
+    pub fn pseudo(mut self, pseudo_class: PseudoClass) -> Self {
+        self.parts.push(Part::PseudoClass(pseudo_class));
+
+        self
+    }
+}
+
+impl From<Node> for Compound {
+    fn from(node: Node) -> Self {
+        Self::new().node(node)
+    }
+}
+
+impl From<PseudoClass> for Selector {
+    fn from(pseudo_class: PseudoClass) -> Self {
+        Self::new().pseudo(pseudo_class)
+    }
+}
+
+impl Node {
+    #[must_use]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Any => "*",
+            Self::Box => "box",
+            Self::Button => "button",
+            Self::Label => "label",
+            Self::Image => "image",
+            Self::Entry => "entry",
+            Self::Text => "text",
+            Self::Scale => "scale",
+            Self::Trough => "trough",
+            Self::Highlight => "highlight",
+            Self::Slider => "slider",
+            Self::Progress => "progress",
+            Self::ProgressBar => "progressbar",
+            Self::Row => "row",
+            Self::ListView => "listview",
+            Self::Popover => "popover",
+            Self::Contents => "contents",
+            Self::Arrow => "arrow",
+            Self::ScrolledWindow => "scrolledwindow",
+            Self::Scrollbar => "scrollbar",
+            Self::Undershoot => "undershoot",
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in &self.parts {
+            write!(f, "{part}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Compound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.node {
+            Some(node) => f.write_str(node.name())?,
+            None if self.parts.is_empty() => f.write_str("*")?,
+            None => {}
+        }
+
+        for part in &self.parts {
+            write!(f, "{part}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Class(class) => write!(f, ".{class}"),
+            Self::PseudoClass(pseudo_class) => write!(f, ":{pseudo_class}"),
+            Self::Not(compound) => write!(f, ":not({compound})"),
+            Self::Node(combinator, node) => write!(f, "{combinator}{}", node.name()),
+        }
+    }
+}
+
+impl fmt::Display for Combinator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Descendant => " ",
+            Self::Child => " > ",
+            Self::NextSibling => " + ",
+            Self::Sibling => " ~ ",
+        })
+    }
+}
+
+impl fmt::Display for PseudoClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Hover => f.write_str("hover"),
+            Self::Active => f.write_str("active"),
+            Self::Focus => f.write_str("focus"),
+            Self::FocusWithin => f.write_str("focus-within"),
+            Self::FocusVisible => f.write_str("focus-visible"),
+            Self::Checked => f.write_str("checked"),
+            Self::Selected => f.write_str("selected"),
+            Self::Disabled => f.write_str("disabled"),
+            Self::Backdrop => f.write_str("backdrop"),
+            Self::FirstChild => f.write_str("first-child"),
+            Self::LastChild => f.write_str("last-child"),
+            Self::OnlyChild => f.write_str("only-child"),
+            Self::NthChild(a, b) => write!(f, "nth-child({a}n{b:+})"),
+            Self::NthLastChild(a, b) => write!(f, "nth-last-child({a}n{b:+})"),
+        }
+    }
+}
@@ -1,12 +1,22 @@
-use material_colors::color::Argb;
-
-use crate::utils::ColorExt;
+use std::{array, collections::BTreeMap};
 
-use super::{border::{Border, BorderRadius}, font::FontWeight, thickness::Thickness};
-
-#[derive(Debug, Default)]
+use material_colors::color::Argb;
+use tracing::warn;
+
+use super::{
+    background::{BlendMode, Gradient, Image},
+    border::{Border, BorderRadius},
+    font::FontWeight,
+    length::Length,
+    property::{Property, Value},
+    thickness::Thickness,
+    token::{Elevation, Shape, Typescale},
+    transition::Transition,
+};
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Style {
-    pub(super) properties: Vec<String>,
+    properties: BTreeMap<Property, Value>,
 }
 
 impl Style {
@@ -15,147 +25,288 @@ impl Style {
         Self::default()
     }
 
-    pub(super) fn with_class_name(self, class_name: &str) -> String {
-        format!("{class_name} {{ {} }}", self.properties.join("\n"))
+    #[must_use]
+    pub fn with_class_name(&self, class_name: &str) -> String {
+        format!("{class_name} {{ {} }}", self.declarations())
     }
 
-    #[must_use]
-    pub fn background_image(mut self, url: &str) -> Self {
+    pub(super) fn declarations(&self) -> String {
         self.properties
-            .push(format!("background-image: url(\"file://{url}\");"));
-
-        self
+            .iter()
+            .map(|(property, value)| format!("{property}: {value};"))
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 
+    // Replaces any earlier value of the property.
     #[must_use]
-    pub fn background_size(mut self, size: &str) -> Self {
-        self.properties.push(format!("background-size: {size};"));
+    pub fn set(mut self, property: Property, value: Value) -> Self {
+        self.properties.insert(property, value);
 
         self
     }
 
     #[must_use]
-    pub fn background_repeat(mut self, repeat: &str) -> Self {
-        self.properties
-            .push(format!("background-repeat: {repeat};"));
+    pub fn without(mut self, property: Property) -> Self {
+        self.properties.remove(&property);
 
         self
     }
 
+    // Properties of `other` win over the ones already set.
     #[must_use]
-    pub fn background_position(mut self, position: &str) -> Self {
-        self.properties
-            .push(format!("background-position: {position};"));
+    pub fn merge(mut self, other: &Self) -> Self {
+        self.extend(other.clone());
 
         self
     }
 
+    pub fn extend(&mut self, other: Self) {
+        self.properties.extend(other.properties);
+    }
+
     #[must_use]
-    pub fn background_color(mut self, color: Argb) -> Self {
-        self.properties
-            .push(format!("background-color: {};", color.to_rgba()));
+    pub fn get(&self, property: Property) -> Option<&Value> {
+        self.properties.get(&property)
+    }
 
-        self
+    pub fn properties(&self) -> impl Iterator<Item = (Property, &Value)> {
+        self.properties
+            .iter()
+            .map(|(property, value)| (*property, value))
     }
 
+    // The properties that have to change to turn this style into `other`,
+    // `None` for the ones `other` doesn't set.
     #[must_use]
-    pub fn color(mut self, color: Argb) -> Self {
-        self.properties.push(format!("color: {};", color.to_rgba()));
+    pub fn diff<'a>(&self, other: &'a Self) -> Vec<(Property, Option<&'a Value>)> {
+        let removed = self
+            .properties
+            .keys()
+            .filter(|property| !other.properties.contains_key(*property))
+            .map(|property| (*property, None));
+        let changed = other
+            .properties()
+            .filter(|(property, value)| self.get(*property) != Some(*value))
+            .map(|(property, value)| (property, Some(value)));
+
+        let mut diff = removed.chain(changed).collect::<Vec<_>>();
+
+        diff.sort_by_key(|(property, _)| *property);
+
+        diff
+    }
 
-        self
+    fn set_all(self, properties: [Property; 4], values: [Option<Length>; 4]) -> Self {
+        properties
+            .into_iter()
+            .zip(values)
+            .fold(self, |style, (property, value)| match value {
+                Some(value) => style.set(property, Value::Length(value)),
+                None => style,
+            })
     }
 
     #[must_use]
-    pub fn border_radius(mut self, radius: &BorderRadius) -> Self {
-        self.properties.push(radius.to_string());
+    pub fn background_image(self, path: &str) -> Self {
+        self.background_layers(&[Image::File(path.into())])
+    }
 
-        self
+    #[must_use]
+    pub fn background_gradient(self, gradient: Gradient) -> Self {
+        self.background_layers(&[gradient.into()])
     }
 
+    // Layers are drawn on top of each other, the first one on top, with the
+    // background color below all of them.
     #[must_use]
-    pub fn font_size(mut self, size: u32) -> Self {
-        self.properties.push(format!("font-size: {size}px;"));
+    pub fn background_layers(self, layers: &[Image]) -> Self {
+        self.set(Property::BackgroundImage, Value::Images(layers.to_vec()))
+    }
 
-        self
+    // One mode per layer, repeated when there are fewer modes than layers.
+    #[must_use]
+    pub fn background_blend_mode(self, modes: &[BlendMode]) -> Self {
+        self.set(
+            Property::BackgroundBlendMode,
+            Value::BlendModes(modes.to_vec()),
+        )
     }
 
     #[must_use]
-    pub fn min_width(mut self, width: u32) -> Self {
-        self.properties.push(format!("min-width: {width}px;"));
+    pub fn background_size(self, size: &str) -> Self {
+        self.set(Property::BackgroundSize, Value::Keyword(size.into()))
+    }
 
-        self
+    #[must_use]
+    pub fn background_repeat(self, repeat: &str) -> Self {
+        self.set(Property::BackgroundRepeat, Value::Keyword(repeat.into()))
     }
 
     #[must_use]
-    pub fn min_height(mut self, height: u32) -> Self {
-        self.properties.push(format!("min-height: {height}px;"));
+    pub fn background_position(self, position: &str) -> Self {
+        self.set(
+            Property::BackgroundPosition,
+            Value::Keyword(position.into()),
+        )
+    }
 
-        self
+    #[must_use]
+    pub fn background_color(self, color: Argb) -> Self {
+        self.set(Property::BackgroundColor, Value::Color(color))
     }
 
     #[must_use]
-    pub fn min_size(mut self, size: u32) -> Self {
-        self.properties.push(format!("min-width: {size}px;"));
-        self.properties.push(format!("min-height: {size}px;"));
+    pub fn color(self, color: Argb) -> Self {
+        self.set(Property::Color, Value::Color(color))
+    }
 
-        self
+    // CSS rejects negative radii, sizes, paddings, border widths and shadow
+    // blurs, GTK would drop the whole declaration. Such lengths are clamped
+    // to zero here, see `Length::non_negative`.
+    #[must_use]
+    pub fn border_radius(self, radius: &BorderRadius) -> Self {
+        self.set_all(
+            Property::BORDER_RADIUS,
+            radius
+                .corners()
+                .map(|corner| corner.map(Length::non_negative)),
+        )
     }
 
     #[must_use]
-    pub fn margin(mut self, thickness: &Thickness) -> Self {
-        self.properties.push(format!("margin{thickness}"));
+    pub fn font_size(self, size: impl Into<Length>) -> Self {
+        self.set(
+            Property::FontSize,
+            Value::Length(size.into().non_negative()),
+        )
+    }
 
-        self
+    #[must_use]
+    pub fn min_width(self, width: impl Into<Length>) -> Self {
+        match absolute(Property::MinWidth, width.into()) {
+            Some(width) => self.set(Property::MinWidth, Value::Length(width.non_negative())),
+            None => self,
+        }
     }
 
     #[must_use]
-    pub fn padding(mut self, thickness: &Thickness) -> Self {
-        self.properties.push(format!("padding{thickness}"));
+    pub fn min_height(self, height: impl Into<Length>) -> Self {
+        match absolute(Property::MinHeight, height.into()) {
+            Some(height) => self.set(Property::MinHeight, Value::Length(height.non_negative())),
+            None => self,
+        }
+    }
 
-        self
+    #[must_use]
+    pub fn min_size(self, size: impl Into<Length>) -> Self {
+        let size = size.into();
+
+        self.min_width(size).min_height(size)
     }
 
+    // Margins may be negative.
     #[must_use]
-    pub fn border(mut self, border: &Border) -> Self {
-        self.properties.push(format!("border: {border};"));
+    pub fn margin(self, thickness: &Thickness) -> Self {
+        self.set_all(
+            Property::MARGIN,
+            absolute_sides(Property::MARGIN, thickness.sides()),
+        )
+    }
 
-        self
+    #[must_use]
+    pub fn padding(self, thickness: &Thickness) -> Self {
+        self.set_all(
+            Property::PADDING,
+            absolute_sides(Property::PADDING, thickness.sides())
+                .map(|side| side.map(Length::non_negative)),
+        )
     }
 
     #[must_use]
-    pub fn box_shadow(mut self, length: Vec<u32>, color: Argb) -> Self {
-        self.properties.push(format!(
-            "box-shadow: {} {};",
-            length
-                .into_iter()
-                .map(|value| format!("{value}px"))
-                .collect::<Vec<_>>()
-                .join(" "),
-            color.to_rgba()
-        ));
+    pub fn border(self, border: &Border) -> Self {
+        self.set(Property::Border, Value::Border(border.clone()))
+    }
 
-        self
+    // The lengths are the x and y offsets followed by the optional blur and
+    // spread, only the offsets and the spread may be negative.
+    #[must_use]
+    pub fn box_shadow<L: Into<Length>>(
+        self,
+        lengths: impl IntoIterator<Item = L>,
+        color: Argb,
+    ) -> Self {
+        let lengths = lengths
+            .into_iter()
+            .map(|length| absolute(Property::BoxShadow, length.into()))
+            .enumerate()
+            .map(|(index, length)| {
+                if index == 2 {
+                    length.map(Length::non_negative)
+                } else {
+                    length
+                }
+            })
+            .collect::<Option<Vec<_>>>();
+
+        match lengths {
+            Some(lengths) => self.set(Property::BoxShadow, Value::Shadow(lengths, color)),
+            None => self,
+        }
     }
 
     #[must_use]
-    pub fn font_family(mut self, family: &str) -> Self {
-        self.properties.push(format!("font-family: {family};"));
+    pub fn font_family(self, family: &str) -> Self {
+        self.set(Property::FontFamily, Value::Keyword(family.into()))
+    }
 
-        self
+    #[must_use]
+    pub fn font_weight(self, weight: &FontWeight) -> Self {
+        self.set(Property::FontWeight, Value::FontWeight(*weight))
     }
 
     #[must_use]
-    pub fn font_weight(mut self, weight: &FontWeight) -> Self {
-        self.properties.push(format!("font-weight: {weight};"));
+    pub fn typescale(self, role: Typescale) -> Self {
+        self.font_size(role.size()).font_weight(&role.weight())
+    }
 
-        self
+    #[must_use]
+    pub fn shape(self, shape: Shape) -> Self {
+        self.border_radius(&BorderRadius::All(shape.radius()))
     }
 
-    // TODO: replace with Transition struct/enum/idk
     #[must_use]
-    pub fn transition(mut self, transition: &str) -> Self {
-        self.properties.push(format!("transition: {transition};"));
+    pub fn elevation(self, elevation: Elevation, color: Argb) -> Self {
+        self.box_shadow(
+            [
+                Length::ZERO,
+                Length::ZERO,
+                Length::Px(f64::from(elevation.blur())),
+            ],
+            color,
+        )
+    }
 
-        self
+    #[must_use]
+    pub fn transition(self, transitions: &[Transition]) -> Self {
+        self.set(
+            Property::Transition,
+            Value::Transitions(transitions.to_vec()),
+        )
     }
-}
\ No newline at end of file
+}
+
+// `length` unless it is a percentage `property` doesn't take.
+fn absolute(property: Property, length: Length) -> Option<Length> {
+    let absolute = length.absolute();
+
+    if absolute.is_none() {
+        warn!(%property, %length, "percentages are not supported here, leaving it out");
+    }
+
+    absolute
+}
+
+fn absolute_sides(properties: [Property; 4], sides: [Option<Length>; 4]) -> [Option<Length>; 4] {
+    array::from_fn(|index| sides[index].and_then(|side| absolute(properties[index], side)))
+}
@@ -1,8 +1,8 @@
-use super::style::Style;
+use super::{selector::Selector, style::Style};
 
 #[derive(Default)]
 pub struct StyleSheet {
-    pub(super) styles: Vec<(Option<String>, Style)>,
+    pub(super) styles: Vec<(Selector, Style)>,
 }
 
 impl StyleSheet {
@@ -11,33 +11,23 @@ impl StyleSheet {
         Self::default()
     }
 
-    pub(super)  fn with_class_name(self, class_name: &str) -> String {
+    #[must_use]
+    pub fn with_class_name(&self, class_name: &str) -> String {
         self.styles
-            .into_iter()
-            .map(|(selector, style)| {
-                if let Some(selector) = selector {
-                    format!(
-                        "{class_name}{selector} {{ {} }}",
-                        style.properties.join("\n")
-                    )
-                } else {
-                    style.with_class_name(class_name)
-                }
-            })
+            .iter()
+            .map(|(selector, style)| style.with_class_name(&format!("{class_name}{selector}")))
             .collect::<Vec<_>>()
             .join("\n\n")
     }
 
     #[must_use]
-    pub fn default_style(mut self, style: Style) -> Self {
-        self.styles.push((None, style));
-
-        self
+    pub fn default_style(self, style: Style) -> Self {
+        self.style_for(Selector::new(), style)
     }
 
     #[must_use]
-    pub fn style_for(mut self, selector: &str, style: Style) -> Self {
-        self.styles.push((Some(selector.to_string()), style));
+    pub fn style_for(mut self, selector: impl Into<Selector>, style: Style) -> Self {
+        self.styles.push((selector.into(), style));
 
         self
     }
@@ -1,28 +1,29 @@
-use std::fmt;
+use super::length::Length;
 
+// `Custom` follows the CSS order: top, right, bottom and left.
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Thickness {
-    All(u32),
-    Custom(u32, u32, u32, u32),
-    Left(u32),
-    Top(u32),
-    Right(u32),
-    Bottom(u32),
+    All(Length),
+    Custom(Length, Length, Length, Length),
+    Left(Length),
+    Top(Length),
+    Right(Length),
+    Bottom(Length),
 }
 
-impl fmt::Display for Thickness {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Self::All(radius) => write!(f, ": {radius}px;"),
-            Self::Custom(top_left, top_right, bottom_left, bottom_right) => {
-                write!(
-                    f,
-                    ": {top_left}px {top_right}px {bottom_left}px {bottom_right}px;"
-                )
+impl Thickness {
+    // Top, right, bottom and left, `None` for the sides it leaves alone.
+    #[must_use]
+    pub const fn sides(&self) -> [Option<Length>; 4] {
+        match *self {
+            Self::All(value) => [Some(value); 4],
+            Self::Custom(top, right, bottom, left) => {
+                [Some(top), Some(right), Some(bottom), Some(left)]
             }
-            Self::Left(value) => write!(f, "-left: {value}px;"),
-            Self::Top(value) => write!(f, "-top: {value}px;"),
-            Self::Right(value) => write!(f, "-right: {value}px;"),
-            Self::Bottom(value) => write!(f, "-bottom: {value}px;"),
+            Self::Left(value) => [None, None, None, Some(value)],
+            Self::Top(value) => [Some(value), None, None, None],
+            Self::Right(value) => [None, Some(value), None, None],
+            Self::Bottom(value) => [None, None, Some(value), None],
         }
     }
 }
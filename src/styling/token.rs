@@ -0,0 +1,157 @@
+use serde::{de, Deserialize, Deserializer};
+
+use crate::theme;
+
+use super::{font::FontWeight, length::Length};
+
+// Radius large enough to fully round any widget of the bar, GTK shrinks it to
+// fit like browsers do.
+const FULL_RADIUS: Length = Length::Px(9999.0);
+
+// The Material 3 type scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Typescale {
+    DisplayLarge,
+    DisplayMedium,
+    DisplaySmall,
+    HeadlineLarge,
+    HeadlineMedium,
+    HeadlineSmall,
+    TitleLarge,
+    TitleMedium,
+    TitleSmall,
+    BodyLarge,
+    BodyMedium,
+    BodySmall,
+    LabelLarge,
+    LabelMedium,
+    LabelSmall,
+}
+
+// The Material 3 corner radius scale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    None,
+    ExtraSmall,
+    Small,
+    Medium,
+    Large,
+    ExtraLarge,
+    Full,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Elevation {
+    Level0,
+    Level1,
+    Level2,
+    Level3,
+    Level4,
+    Level5,
+}
+
+// Multiplies every token size, `1.5` makes text, corners and shadows half
+// again as large.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Scale(f64);
+
+impl Typescale {
+    const fn base(self) -> (u32, FontWeight) {
+        match self {
+            Self::DisplayLarge => (57, FontWeight::Normal),
+            Self::DisplayMedium => (45, FontWeight::Normal),
+            Self::DisplaySmall => (36, FontWeight::Normal),
+            Self::HeadlineLarge => (32, FontWeight::Normal),
+            Self::HeadlineMedium => (28, FontWeight::Normal),
+            Self::HeadlineSmall => (24, FontWeight::Normal),
+            Self::TitleLarge => (22, FontWeight::Normal),
+            Self::TitleMedium => (16, FontWeight::Medium),
+            Self::TitleSmall => (14, FontWeight::Medium),
+            Self::BodyLarge => (16, FontWeight::Normal),
+            Self::BodyMedium => (14, FontWeight::Normal),
+            Self::BodySmall => (12, FontWeight::Normal),
+            Self::LabelLarge => (14, FontWeight::Medium),
+            Self::LabelMedium => (12, FontWeight::Medium),
+            Self::LabelSmall => (11, FontWeight::Medium),
+        }
+    }
+
+    #[must_use]
+    pub fn size(self) -> Length {
+        scaled(self.base().0)
+    }
+
+    #[must_use]
+    pub const fn weight(self) -> FontWeight {
+        self.base().1
+    }
+}
+
+impl Shape {
+    #[must_use]
+    pub fn radius(self) -> Length {
+        let radius = match self {
+            Self::None => 0,
+            Self::ExtraSmall => 4,
+            Self::Small => 8,
+            Self::Medium => 12,
+            Self::Large => 16,
+            Self::ExtraLarge => 28,
+            Self::Full => return FULL_RADIUS,
+        };
+
+        scaled(radius)
+    }
+}
+
+impl Elevation {
+    // The shadow blur, the bar draws elevation as a glow around the surface
+    // rather than an offset shadow.
+    #[must_use]
+    pub fn blur(self) -> u32 {
+        let blur = match self {
+            Self::Level0 => 0,
+            Self::Level1 => 1,
+            Self::Level2 => 3,
+            Self::Level3 => 6,
+            Self::Level4 => 8,
+            Self::Level5 => 12,
+        };
+
+        theme::scale().apply(blur)
+    }
+}
+
+impl Scale {
+    #[must_use]
+    pub fn apply(self, size: u32) -> u32 {
+        (f64::from(size) * self.0).round() as u32
+    }
+}
+
+impl Default for Scale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Scale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let scale = f64::deserialize(deserializer)?;
+
+        if scale.is_finite() && scale > 0.0 {
+            Ok(Self(scale))
+        } else {
+            Err(de::Error::custom(format!(
+                "invalid scale `{scale}`, expected a positive number"
+            )))
+        }
+    }
+}
+
+// A size in pixels multiplied by the theme scale, for the sizes the tokens
+// don't cover.
+#[must_use]
+pub fn scaled(size: u32) -> Length {
+    Length::Px(f64::from(theme::scale().apply(size)))
+}
@@ -0,0 +1,300 @@
+use std::{fmt, str::FromStr, time::Duration};
+
+use serde::{de, Deserialize, Deserializer};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Easing {
+    Linear,
+    #[default]
+    Ease,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    StepStart,
+    StepEnd,
+    CubicBezier(f64, f64, f64, f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    property: String,
+    duration: Duration,
+    easing: Easing,
+    delay: Duration,
+}
+
+// The animations of the bar, user themes can slow them down or turn them off
+// with a zero duration.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Transitions {
+    // Hover, focus and workspace state changes.
+    pub state: Transition,
+    // Colors following a new album art or theme.
+    pub colors: Transition,
+}
+
+impl Transition {
+    #[must_use]
+    pub fn new(property: &str) -> Self {
+        Self {
+            property: property.into(),
+            duration: Duration::ZERO,
+            easing: Easing::default(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    #[must_use]
+    pub fn property(mut self, property: &str) -> Self {
+        property.clone_into(&mut self.property);
+
+        self
+    }
+
+    #[must_use]
+    pub const fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+
+        self
+    }
+
+    #[must_use]
+    pub const fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+
+        self
+    }
+
+    #[must_use]
+    pub const fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+
+        self
+    }
+
+    // The same timing applied to other properties.
+    #[must_use]
+    pub fn for_property(&self, property: &str) -> Self {
+        self.clone().property(property)
+    }
+
+    #[must_use]
+    pub fn for_properties(&self, properties: &[&str]) -> Vec<Self> {
+        properties
+            .iter()
+            .map(|property| self.for_property(property))
+            .collect()
+    }
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self::new("all")
+    }
+}
+
+impl Default for Transitions {
+    fn default() -> Self {
+        Self {
+            state: Transition::default().duration(Duration::from_millis(300)),
+            colors: Transition::default().duration(Duration::from_secs(1)),
+        }
+    }
+}
+
+// `[property] [duration] [easing] [delay]` in any order, like in CSS the first
+// time is the duration and the second one the delay.
+impl FromStr for Transition {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut transition = Self::default();
+        let mut property = None;
+        let mut times = vec![];
+
+        for token in split(input) {
+            if let Some(time) = parse_time(token) {
+                times.push(time);
+            } else if let Ok(easing) = token.parse() {
+                transition.easing = easing;
+            } else if property.is_none() && is_property(token) {
+                property = Some(token);
+            } else {
+                return Err(format!(
+                    "invalid transition `{}`, unexpected `{token}`",
+                    input.trim()
+                ));
+            }
+        }
+
+        match times.as_slice() {
+            [] => {}
+            [duration] => transition.duration = *duration,
+            [duration, delay] => {
+                transition.duration = *duration;
+                transition.delay = *delay;
+            }
+            _ => {
+                return Err(format!(
+                    "invalid transition `{}`, expected at most a duration and a delay",
+                    input.trim()
+                ))
+            }
+        }
+
+        if let Some(property) = property {
+            transition.property = property.into();
+        }
+
+        Ok(transition)
+    }
+}
+
+impl FromStr for Easing {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "linear" => Ok(Self::Linear),
+            "ease" => Ok(Self::Ease),
+            "ease-in" => Ok(Self::EaseIn),
+            "ease-out" => Ok(Self::EaseOut),
+            "ease-in-out" => Ok(Self::EaseInOut),
+            "step-start" => Ok(Self::StepStart),
+            "step-end" => Ok(Self::StepEnd),
+            _ => {
+                let points = input
+                    .strip_prefix("cubic-bezier(")
+                    .and_then(|points| points.strip_suffix(')'))
+                    .map(|points| {
+                        points
+                            .split(',')
+                            .map(|point| point.trim().parse::<f64>().ok())
+                            .collect::<Option<Vec<_>>>()
+                    });
+
+                match points {
+                    Some(Some(points)) => match points.as_slice() {
+                        [x1, y1, x2, y2] if (0.0..=1.0).contains(x1) && (0.0..=1.0).contains(x2) => {
+                            Ok(Self::CubicBezier(*x1, *y1, *x2, *y2))
+                        }
+                        _ => Err(format!(
+                            "invalid easing `{input}`, expected four numbers with both x values between 0 and 1"
+                        )),
+                    },
+                    _ => Err(format!(
+                        "invalid easing `{input}`, expected `linear`, `ease`, `ease-in`, `ease-out`, `ease-in-out`, `step-start`, `step-end` or `cubic-bezier(x1, y1, x2, y2)`"
+                    )),
+                }
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Transition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Easing {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl fmt::Display for Transition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}ms {}",
+            self.property,
+            self.duration.as_millis(),
+            self.easing
+        )?;
+
+        if !self.delay.is_zero() {
+            write!(f, " {}ms", self.delay.as_millis())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Easing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Linear => f.write_str("linear"),
+            Self::Ease => f.write_str("ease"),
+            Self::EaseIn => f.write_str("ease-in"),
+            Self::EaseOut => f.write_str("ease-out"),
+            Self::EaseInOut => f.write_str("ease-in-out"),
+            Self::StepStart => f.write_str("step-start"),
+            Self::StepEnd => f.write_str("step-end"),
+            // The x values have to stay between 0 and 1, which `from_str`
+            // checks but a directly built curve may not.
+            Self::CubicBezier(x1, y1, x2, y2) => write!(
+                f,
+                "cubic-bezier({}, {y1}, {}, {y2})",
+                x1.clamp(0.0, 1.0),
+                x2.clamp(0.0, 1.0)
+            ),
+        }
+    }
+}
+
+// Splits on whitespace outside of parentheses, so `cubic-bezier(...)` stays in
+// one piece.
+fn split(input: &str) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0_usize;
+    let mut start = 0;
+
+    for (index, char) in input.char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 && char.is_whitespace() => {
+                parts.push(&input[start..index]);
+                start = index + char.len_utf8();
+            }
+            _ => {}
+        }
+    }
+
+    parts.push(&input[start..]);
+
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect()
+}
+
+fn parse_time(token: &str) -> Option<Duration> {
+    let (value, scale) = token
+        .strip_suffix("ms")
+        .map(|value| (value, 0.001))
+        .or_else(|| token.strip_suffix('s').map(|value| (value, 1.0)))?;
+
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 0.0)
+        .map(|value| Duration::from_secs_f64(value * scale))
+}
+
+// A CSS identifier, it may start with dashes but not with a digit.
+fn is_property(token: &str) -> bool {
+    token
+        .trim_start_matches('-')
+        .starts_with(|char: char| char.is_ascii_alphabetic())
+        && token
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '-')
+}
@@ -0,0 +1,54 @@
+use std::path::{Path, PathBuf};
+
+use crate::{config::Config, utils::watch_file};
+
+use super::USER_PRIORITY;
+
+pub struct UserStyleSheet {
+    provider: gtk::CssProvider,
+    path: PathBuf,
+    _monitor: Option<gio::FileMonitor>,
+}
+
+impl UserStyleSheet {
+    #[must_use]
+    pub fn path() -> PathBuf {
+        Config::directory().join("style.css")
+    }
+
+    // User rules are added for the whole display above the generated
+    // providers, so they win over every `set_style`/`set_stylesheet` call.
+    #[must_use]
+    pub fn load(display: &gdk::Display, path: &Path) -> Self {
+        let provider = gtk::CssProvider::new();
+
+        gtk::style_context_add_provider_for_display(display, &provider, USER_PRIORITY);
+
+        let monitor = watch_file(path, {
+            let provider = provider.clone();
+            let path = path.to_path_buf();
+
+            move || load_provider(&provider, &path)
+        });
+
+        load_provider(&provider, path);
+
+        Self {
+            provider,
+            path: path.into(),
+            _monitor: monitor,
+        }
+    }
+
+    pub fn reload(&self) {
+        load_provider(&self.provider, &self.path);
+    }
+}
+
+fn load_provider(provider: &gtk::CssProvider, path: &Path) {
+    if path.exists() {
+        provider.load_from_path(path);
+    } else {
+        provider.load_from_string("");
+    }
+}
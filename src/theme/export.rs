@@ -0,0 +1,124 @@
+use std::{
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use material_colors::{color::Argb, scheme::Scheme};
+
+use crate::utils::ColorExt;
+
+use super::{roles, source, theme};
+
+// Placeholders look like `{{primary}}` or `{{on_surface.rgba}}`, without a
+// format the color is written as hex. `source` is the seed color. The bare
+// formats leave out the `#`, for configs like Hyprland's `rgba(rrggbbaa)`.
+pub const FORMATS: [&str; 5] = ["hex", "hex_stripped", "rgba_hex", "rgb", "rgba"];
+
+#[derive(Debug)]
+pub enum ExportError {
+    Read(PathBuf, io::Error),
+    Write(PathBuf, io::Error),
+    Template(PathBuf, TemplateError),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    UnknownRole(usize, String),
+    UnknownFormat(usize, String),
+    Unclosed(usize),
+}
+
+// Renders the template at `path` with the active theme.
+pub fn render_file(path: &Path) -> Result<String, ExportError> {
+    let template =
+        fs::read_to_string(path).map_err(|error| ExportError::Read(path.into(), error))?;
+
+    render(&template, &theme(), source()).map_err(|error| ExportError::Template(path.into(), error))
+}
+
+pub fn write(path: &Path, contents: &str) -> Result<(), ExportError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|error| ExportError::Write(path.into(), error))?;
+    }
+
+    fs::write(path, contents).map_err(|error| ExportError::Write(path.into(), error))
+}
+
+pub fn render(template: &str, scheme: &Scheme, source: Argb) -> Result<String, TemplateError> {
+    let mut colors = roles(scheme);
+
+    colors.push(("source", source));
+
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        let line = line_of(template, rest, start);
+
+        output.push_str(&rest[..start]);
+        rest = &rest[start + 2..];
+
+        let end = rest.find("}}").ok_or(TemplateError::Unclosed(line))?;
+        let placeholder = rest[..end].trim();
+        let (role, format) = placeholder.split_once('.').unwrap_or((placeholder, "hex"));
+
+        let color = colors
+            .iter()
+            .find(|(name, _)| *name == role)
+            .map(|(_, color)| color)
+            .ok_or_else(|| TemplateError::UnknownRole(line, role.into()))?;
+
+        output.push_str(&match format {
+            "hex" => color.to_hex(),
+            "hex_stripped" => color.to_hex()[1..].into(),
+            "rgba_hex" => format!("{}{:02x}", &color.to_hex()[1..], color.alpha),
+            "rgb" => format!("rgb({}, {}, {})", color.red, color.green, color.blue),
+            "rgba" => color.to_rgba(),
+            _ => return Err(TemplateError::UnknownFormat(line, format.into())),
+        });
+
+        rest = &rest[end + 2..];
+    }
+
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+// The line of `offset` in `rest`, which is a suffix of `template`.
+fn line_of(template: &str, rest: &str, offset: usize) -> usize {
+    let position = template.len() - rest.len() + offset;
+
+    template[..position].matches('\n').count() + 1
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read(path, error) => write!(f, "failed to read {}: {error}", path.display()),
+            Self::Write(path, error) => write!(f, "failed to write {}: {error}", path.display()),
+            Self::Template(path, error) => write!(f, "{}: {error}", path.display()),
+        }
+    }
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownRole(line, role) => write!(
+                f,
+                "line {line}: unknown color role `{role}`, run `barry theme` to list them"
+            ),
+            Self::UnknownFormat(line, format) => write!(
+                f,
+                "line {line}: unknown color format `{format}`, expected one of {}",
+                FORMATS.join(", ")
+            ),
+            Self::Unclosed(line) => write!(f, "line {line}: placeholder is missing its `}}}}`"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl std::error::Error for TemplateError {}
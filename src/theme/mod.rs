@@ -0,0 +1,268 @@
+use std::{cell::RefCell, path::Path, sync::mpsc, thread, time::Duration};
+
+use material_colors::{
+    color::Argb,
+    image::{FilterType, ImageReader},
+    scheme::Scheme,
+};
+use relm4::{Sender, SharedState};
+use tracing::{debug, warn};
+use zbus::Connection;
+
+use crate::{
+    config::ThemeConfig,
+    styling::{token::Scale, transition::Transitions},
+    utils::watch_file,
+};
+
+pub mod export;
+pub mod portal;
+pub mod scheme;
+
+pub use portal::Appearance;
+pub use scheme::{roles, ColorScheme, Contrast, Mode, SchemeOptions, SchemeVariant};
+
+pub const DEFAULT_SOURCE: u32 = 0x2C563E;
+
+const PORTAL_TIMEOUT: Duration = Duration::from_secs(1);
+
+// Wallpapers are scaled down to this size before their colors are quantized.
+const WALLPAPER_SIZE: u32 = 128;
+
+static THEME: SharedState<Theme> = SharedState::new();
+
+thread_local! {
+    static WALLPAPER: RefCell<Option<gio::FileMonitor>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ThemeChanged;
+
+// `seed` is an explicitly chosen source color, without one the theme follows
+// the system accent color and falls back to `DEFAULT_SOURCE`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThemeSettings {
+    pub seed: Option<Argb>,
+    pub options: SchemeOptions,
+    pub appearance: Appearance,
+}
+
+#[derive(Debug, Clone)]
+pub struct Theme {
+    pub settings: ThemeSettings,
+    pub scheme: Scheme,
+    pub transitions: Transitions,
+    pub scale: Scale,
+}
+
+impl ThemeSettings {
+    #[must_use]
+    pub fn source(&self) -> Argb {
+        self.seed
+            .or(self.appearance.accent)
+            .unwrap_or_else(|| Argb::from_u32(DEFAULT_SOURCE))
+    }
+}
+
+impl Theme {
+    #[must_use]
+    pub fn new(settings: ThemeSettings) -> Self {
+        Self {
+            settings,
+            scheme: settings
+                .options
+                .scheme(settings.source(), settings.appearance.color_scheme),
+            transitions: Transitions::default(),
+            scale: Scale::default(),
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::new(ThemeSettings::default())
+    }
+}
+
+#[must_use]
+pub fn theme() -> Scheme {
+    THEME.read().scheme.clone()
+}
+
+#[must_use]
+pub fn source() -> Argb {
+    THEME.read().settings.source()
+}
+
+#[must_use]
+pub fn settings() -> ThemeSettings {
+    THEME.read().settings
+}
+
+#[must_use]
+pub fn transitions() -> Transitions {
+    THEME.read().transitions.clone()
+}
+
+#[must_use]
+pub fn scale() -> Scale {
+    THEME.read().scale
+}
+
+// Builds a scheme for another source color (album art for example) that
+// follows the configured mode and contrast.
+#[must_use]
+pub fn scheme_for(source: Argb, variant: SchemeVariant) -> Scheme {
+    let settings = settings();
+
+    settings
+        .options
+        .scheme_with_variant(source, variant, settings.appearance.color_scheme)
+}
+
+pub fn set_source(source: Argb) {
+    update(|settings| settings.seed = Some(source));
+}
+
+pub fn set_options(options: SchemeOptions) {
+    update(|settings| settings.options = options);
+}
+
+pub fn set_transitions(transitions: Transitions) {
+    if THEME.read().transitions != transitions {
+        THEME.write().transitions = transitions;
+    }
+}
+
+pub fn set_scale(scale: Scale) {
+    if THEME.read().scale != scale {
+        THEME.write().scale = scale;
+    }
+}
+
+pub fn set_appearance(appearance: Appearance) {
+    update(|settings| settings.appearance = appearance);
+}
+
+fn update<F: FnOnce(&mut ThemeSettings)>(f: F) {
+    let previous = settings();
+    let mut settings = previous;
+
+    f(&mut settings);
+
+    if settings != previous {
+        debug!(?settings, "theme changed");
+
+        let transitions = transitions();
+        let scale = scale();
+
+        *THEME.write() = Theme {
+            transitions,
+            scale,
+            ..Theme::new(settings)
+        };
+    }
+}
+
+// A wallpaper takes precedence over a fixed source color, the color is only
+// used when the image can't be read.
+pub fn configure(config: &ThemeConfig) {
+    WALLPAPER.with_borrow_mut(|monitor| {
+        *monitor = config.wallpaper.as_deref().and_then(watch_wallpaper);
+    });
+
+    let seed = config
+        .wallpaper
+        .as_deref()
+        .and_then(wallpaper_source)
+        .or(config.source.map(|source| source.0));
+
+    update(|settings| {
+        settings.seed = seed;
+        settings.options = config.scheme_options();
+    });
+
+    set_transitions(config.transitions.clone());
+    set_scale(config.scale);
+}
+
+// Follows the color scheme preference and accent color of the desktop. Without
+// a portal the theme just keeps its defaults.
+pub fn follow_portal() {
+    relm4::spawn(async {
+        let result = match Connection::session().await {
+            Ok(connection) => portal::watch(&connection, set_appearance).await,
+            Err(error) => Err(error),
+        };
+
+        if let Err(error) = result {
+            warn!(%error, "failed to read the settings portal");
+        }
+    });
+}
+
+// Applies the current appearance of the desktop once, for commands that exit
+// instead of following changes. A portal that doesn't answer in time is
+// treated like a missing one.
+pub async fn read_portal() {
+    let read = async {
+        let connection = Connection::session().await?;
+        let proxy = portal::SettingsProxy::new(&connection).await?;
+
+        zbus::Result::Ok(portal::appearance(&proxy).await)
+    };
+
+    match tokio::time::timeout(PORTAL_TIMEOUT, read).await {
+        Ok(Ok(appearance)) => set_appearance(appearance),
+        Ok(Err(error)) => warn!(%error, "failed to read the settings portal"),
+        Err(_) => warn!("the settings portal didn't answer in time"),
+    }
+}
+
+#[must_use]
+pub fn wallpaper_source(path: &Path) -> Option<Argb> {
+    let mut image = match ImageReader::open(path) {
+        Ok(image) => image,
+        Err(error) => {
+            warn!(path = %path.display(), %error, "failed to open the wallpaper");
+
+            return None;
+        }
+    };
+
+    image.resize(WALLPAPER_SIZE, WALLPAPER_SIZE, FilterType::Nearest);
+
+    Some(ImageReader::extract_color(&image))
+}
+
+fn watch_wallpaper(path: &Path) -> Option<gio::FileMonitor> {
+    let (changed, changes) = mpsc::channel::<()>();
+    let wallpaper = path.to_path_buf();
+
+    // Decoding a large image takes a while, so it happens on one worker off
+    // the main loop. Changes that pile up while it's busy are handled once,
+    // and the worker stops together with the monitor.
+    thread::spawn(move || {
+        while changes.recv().is_ok() {
+            changes.try_iter().for_each(drop);
+
+            if let Some(source) = wallpaper_source(&wallpaper) {
+                set_source(source);
+            }
+        }
+    });
+
+    watch_file(path, move || {
+        let _ = changed.send(());
+    })
+}
+
+// Every subscriber gets a message after the theme changes, components are
+// expected to re-read `theme()` and restyle their widgets.
+pub fn subscribe<Msg, F>(sender: &Sender<Msg>, f: F)
+where
+    Msg: Send + 'static,
+    F: Fn(&Scheme) -> Msg + Send + Sync + 'static,
+{
+    THEME.subscribe(sender, move |theme| f(&theme.scheme));
+}
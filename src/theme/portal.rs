@@ -0,0 +1,118 @@
+use material_colors::color::Argb;
+use tokio_stream::StreamExt;
+use zbus::{
+    proxy,
+    zvariant::{OwnedValue, Value},
+    Connection,
+};
+
+use super::scheme::ColorScheme;
+
+pub const NAMESPACE: &str = "org.freedesktop.appearance";
+pub const COLOR_SCHEME: &str = "color-scheme";
+pub const ACCENT_COLOR: &str = "accent-color";
+
+#[proxy(
+    interface = "org.freedesktop.portal.Settings",
+    default_service = "org.freedesktop.portal.Desktop",
+    default_path = "/org/freedesktop/portal/desktop"
+)]
+pub trait Settings {
+    fn read_one(&self, namespace: &str, key: &str) -> zbus::Result<OwnedValue>;
+
+    // Deprecated in favour of `ReadOne`, older portals only implement this one
+    // and wrap the value in another variant.
+    fn read(&self, namespace: &str, key: &str) -> zbus::Result<OwnedValue>;
+
+    #[zbus(signal)]
+    fn setting_changed(&self, namespace: &str, key: &str, value: Value<'_>) -> zbus::Result<()>;
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Appearance {
+    pub color_scheme: ColorScheme,
+    pub accent: Option<Argb>,
+}
+
+pub async fn appearance(proxy: &SettingsProxy<'_>) -> Appearance {
+    Appearance {
+        color_scheme: read(proxy, COLOR_SCHEME)
+            .await
+            .as_deref()
+            .and_then(color_scheme)
+            .unwrap_or_default(),
+        accent: read(proxy, ACCENT_COLOR).await.as_deref().and_then(accent),
+    }
+}
+
+// Calls `f` with the current appearance and again every time the portal
+// reports a change, until the connection goes away.
+pub async fn watch<F: Fn(Appearance)>(connection: &Connection, f: F) -> zbus::Result<()> {
+    let proxy = SettingsProxy::new(connection).await?;
+    let mut changes = proxy.receive_setting_changed().await?;
+    let mut appearance = self::appearance(&proxy).await;
+
+    f(appearance);
+
+    while let Some(change) = changes.next().await {
+        let Ok(args) = change.args() else {
+            continue;
+        };
+
+        if *args.namespace() != NAMESPACE {
+            continue;
+        }
+
+        match *args.key() {
+            COLOR_SCHEME => {
+                appearance.color_scheme = color_scheme(args.value()).unwrap_or_default()
+            }
+            ACCENT_COLOR => appearance.accent = accent(args.value()),
+            _ => continue,
+        }
+
+        f(appearance);
+    }
+
+    Ok(())
+}
+
+async fn read(proxy: &SettingsProxy<'_>, key: &str) -> Option<OwnedValue> {
+    match proxy.read_one(NAMESPACE, key).await {
+        Ok(value) => Some(value),
+        Err(_) => proxy.read(NAMESPACE, key).await.ok(),
+    }
+}
+
+fn color_scheme(value: &Value<'_>) -> Option<ColorScheme> {
+    match value {
+        Value::Value(value) => color_scheme(value),
+        Value::U32(0) => Some(ColorScheme::NoPreference),
+        Value::U32(1) => Some(ColorScheme::Dark),
+        Value::U32(2) => Some(ColorScheme::Light),
+        _ => None,
+    }
+}
+
+// The accent color is an RGB triple in the 0..1 range, anything outside of it
+// means the user didn't pick one.
+fn accent(value: &Value<'_>) -> Option<Argb> {
+    match value {
+        Value::Value(value) => accent(value),
+        Value::Structure(structure) => match structure.fields() {
+            [Value::F64(red), Value::F64(green), Value::F64(blue)] => {
+                let channel = |value: f64| {
+                    (0.0..=1.0)
+                        .contains(&value)
+                        .then(|| (value * 255.0).round() as u32)
+                };
+
+                Some(Argb::from_u32(
+                    0xFF000000 | (channel(*red)? << 16) | (channel(*green)? << 8) | channel(*blue)?,
+                ))
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
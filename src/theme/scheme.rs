@@ -0,0 +1,241 @@
+use std::fmt;
+
+use material_colors::{
+    color::Argb,
+    dynamic_color::{DynamicScheme, Variant},
+    scheme::Scheme,
+};
+use serde::{de, Deserialize, Deserializer};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Mode {
+    Light,
+    #[default]
+    Dark,
+    System,
+}
+
+// The `org.freedesktop.appearance color-scheme` preference.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorScheme {
+    #[default]
+    NoPreference,
+    Dark,
+    Light,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SchemeVariant {
+    Monochrome,
+    Neutral,
+    #[default]
+    TonalSpot,
+    Vibrant,
+    Expressive,
+    Fidelity,
+    Content,
+    Rainbow,
+    FruitSalad,
+}
+
+// Material contrast level, -1.0 is the lowest, 0.0 the default and 1.0 the
+// highest contrast.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Contrast(f64);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SchemeOptions {
+    pub mode: Mode,
+    pub variant: SchemeVariant,
+    pub contrast: Contrast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InvalidContrast(f64);
+
+impl Mode {
+    // Following the system falls back to dark when there is no preference.
+    #[must_use]
+    pub const fn is_dark(self, preference: ColorScheme) -> bool {
+        match self {
+            Self::Light => false,
+            Self::Dark => true,
+            Self::System => !matches!(preference, ColorScheme::Light),
+        }
+    }
+}
+
+impl Contrast {
+    pub const MIN: f64 = -1.0;
+    pub const MAX: f64 = 1.0;
+
+    pub fn new(level: f64) -> Result<Self, InvalidContrast> {
+        if (Self::MIN..=Self::MAX).contains(&level) {
+            Ok(Self(level))
+        } else {
+            Err(InvalidContrast(level))
+        }
+    }
+
+    #[must_use]
+    pub const fn level(self) -> f64 {
+        self.0
+    }
+}
+
+impl SchemeOptions {
+    #[must_use]
+    pub fn scheme(self, source: Argb, preference: ColorScheme) -> Scheme {
+        self.scheme_with_variant(source, self.variant, preference)
+    }
+
+    #[must_use]
+    pub fn scheme_with_variant(
+        self,
+        source: Argb,
+        variant: SchemeVariant,
+        preference: ColorScheme,
+    ) -> Scheme {
+        Scheme::from(DynamicScheme::by_variant(
+            source,
+            &variant.into(),
+            self.mode.is_dark(preference),
+            Some(self.contrast.level()),
+        ))
+    }
+}
+
+#[must_use]
+pub fn roles(scheme: &Scheme) -> Vec<(&'static str, Argb)> {
+    vec![
+        ("primary", scheme.primary),
+        ("on_primary", scheme.on_primary),
+        ("primary_container", scheme.primary_container),
+        ("on_primary_container", scheme.on_primary_container),
+        ("inverse_primary", scheme.inverse_primary),
+        ("primary_fixed", scheme.primary_fixed),
+        ("primary_fixed_dim", scheme.primary_fixed_dim),
+        ("on_primary_fixed", scheme.on_primary_fixed),
+        ("on_primary_fixed_variant", scheme.on_primary_fixed_variant),
+        ("secondary", scheme.secondary),
+        ("on_secondary", scheme.on_secondary),
+        ("secondary_container", scheme.secondary_container),
+        ("on_secondary_container", scheme.on_secondary_container),
+        ("secondary_fixed", scheme.secondary_fixed),
+        ("secondary_fixed_dim", scheme.secondary_fixed_dim),
+        ("on_secondary_fixed", scheme.on_secondary_fixed),
+        (
+            "on_secondary_fixed_variant",
+            scheme.on_secondary_fixed_variant,
+        ),
+        ("tertiary", scheme.tertiary),
+        ("on_tertiary", scheme.on_tertiary),
+        ("tertiary_container", scheme.tertiary_container),
+        ("on_tertiary_container", scheme.on_tertiary_container),
+        ("tertiary_fixed", scheme.tertiary_fixed),
+        ("tertiary_fixed_dim", scheme.tertiary_fixed_dim),
+        ("on_tertiary_fixed", scheme.on_tertiary_fixed),
+        (
+            "on_tertiary_fixed_variant",
+            scheme.on_tertiary_fixed_variant,
+        ),
+        ("error", scheme.error),
+        ("on_error", scheme.on_error),
+        ("error_container", scheme.error_container),
+        ("on_error_container", scheme.on_error_container),
+        ("surface_dim", scheme.surface_dim),
+        ("surface", scheme.surface),
+        ("surface_bright", scheme.surface_bright),
+        ("surface_container_lowest", scheme.surface_container_lowest),
+        ("surface_container_low", scheme.surface_container_low),
+        ("surface_container", scheme.surface_container),
+        ("surface_container_high", scheme.surface_container_high),
+        (
+            "surface_container_highest",
+            scheme.surface_container_highest,
+        ),
+        ("on_surface", scheme.on_surface),
+        ("on_surface_variant", scheme.on_surface_variant),
+        ("outline", scheme.outline),
+        ("outline_variant", scheme.outline_variant),
+        ("inverse_surface", scheme.inverse_surface),
+        ("inverse_on_surface", scheme.inverse_on_surface),
+        ("surface_variant", scheme.surface_variant),
+        ("surface_tint", scheme.surface_tint),
+        ("background", scheme.background),
+        ("on_background", scheme.on_background),
+        ("shadow", scheme.shadow),
+        ("scrim", scheme.scrim),
+    ]
+}
+
+impl From<SchemeVariant> for Variant {
+    fn from(variant: SchemeVariant) -> Self {
+        match variant {
+            SchemeVariant::Monochrome => Self::Monochrome,
+            SchemeVariant::Neutral => Self::Neutral,
+            SchemeVariant::TonalSpot => Self::TonalSpot,
+            SchemeVariant::Vibrant => Self::Vibrant,
+            SchemeVariant::Expressive => Self::Expressive,
+            SchemeVariant::Fidelity => Self::Fidelity,
+            SchemeVariant::Content => Self::Content,
+            SchemeVariant::Rainbow => Self::Rainbow,
+            SchemeVariant::FruitSalad => Self::FruitSalad,
+        }
+    }
+}
+
+// The same names the configuration and the command line take.
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Light => "light",
+            Self::Dark => "dark",
+            Self::System => "system",
+        })
+    }
+}
+
+impl fmt::Display for SchemeVariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Monochrome => "monochrome",
+            Self::Neutral => "neutral",
+            Self::TonalSpot => "tonal-spot",
+            Self::Vibrant => "vibrant",
+            Self::Expressive => "expressive",
+            Self::Fidelity => "fidelity",
+            Self::Content => "content",
+            Self::Rainbow => "rainbow",
+            Self::FruitSalad => "fruit-salad",
+        })
+    }
+}
+
+impl fmt::Display for Contrast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Contrast {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Self::new(f64::deserialize(deserializer)?).map_err(de::Error::custom)
+    }
+}
+
+impl fmt::Display for InvalidContrast {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid contrast `{}`, expected a level between {:.1} and {:.1}",
+            self.0,
+            Contrast::MIN,
+            Contrast::MAX
+        )
+    }
+}
+
+impl std::error::Error for InvalidContrast {}
@@ -2,10 +2,11 @@ use gdk::{
     prelude::{DisplayExt, MonitorExt},
     Rectangle, RGBA,
 };
-use gio::prelude::ListModelExt;
+use gio::prelude::{FileExt, FileMonitorExt, ListModelExt};
 use glib::object::Cast;
 use hyprland::shared::HyprDataActive;
 use material_colors::color::Argb;
+use std::path::Path;
 use sysinfo::System;
 
 #[must_use]
@@ -39,6 +40,30 @@ pub fn get_display_geometry() -> Rectangle {
     }
 }
 
+#[must_use]
+pub fn watch_file<F: Fn() + 'static>(path: &Path, callback: F) -> Option<gio::FileMonitor> {
+    let monitor = gio::File::for_path(path)
+        .monitor_file(
+            gio::FileMonitorFlags::WATCH_MOVES,
+            None::<&gio::Cancellable>,
+        )
+        .ok()?;
+
+    monitor.connect_changed(move |_, _, _, event| {
+        if matches!(
+            event,
+            gio::FileMonitorEvent::ChangesDoneHint
+                | gio::FileMonitorEvent::Created
+                | gio::FileMonitorEvent::MovedIn
+                | gio::FileMonitorEvent::Renamed
+        ) {
+            callback();
+        }
+    });
+
+    Some(monitor)
+}
+
 pub trait SystemExt {
     fn cpu_usage(&self) -> f64;
     fn memory_usage(&self) -> f64;
@@ -60,6 +85,7 @@ pub trait ColorExt {
     #[must_use]
     fn with_alpha(&self, alpha: f64) -> Self;
     fn to_rgba(&self) -> String;
+    fn to_hex(&self) -> String;
     fn as_rgba(&self) -> RGBA;
 }
 
@@ -83,6 +109,10 @@ impl ColorExt for Argb {
         )
     }
 
+    fn to_hex(&self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)
+    }
+
     fn as_rgba(&self) -> RGBA {
         RGBA::new(
             f32::from(self.red) / 255.0,
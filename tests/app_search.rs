@@ -0,0 +1,51 @@
+mod common;
+
+use std::{
+    cell::Cell,
+    env,
+    rc::Rc,
+    time::{Duration, Instant},
+};
+
+use barry::apps::app_search::{self, APP_ID, TOGGLE_ACTION};
+use gio::prelude::*;
+
+#[test]
+fn only_toggles_a_running_launcher() {
+    let (_bus, address) = common::spawn_bus().expect("dbus-daemon is needed to run this test");
+
+    env::set_var("DBUS_SESSION_BUS_ADDRESS", address);
+
+    assert!(!app_search::toggle_running());
+
+    // The first launcher has to become the primary instance, otherwise its
+    // `startup` never runs and no window opens.
+    let primary = gio::Application::new(Some(APP_ID), gio::ApplicationFlags::empty());
+    let toggles = Rc::new(Cell::new(0));
+    let toggle = gio::SimpleAction::new(TOGGLE_ACTION, None);
+
+    {
+        let toggles = toggles.clone();
+
+        toggle.connect_activate(move |_, _| toggles.set(toggles.get() + 1));
+    }
+
+    primary.add_action(&toggle);
+    primary.register(None::<&gio::Cancellable>).unwrap();
+
+    assert!(
+        !primary.is_remote(),
+        "checking for a running launcher registered its name"
+    );
+
+    assert!(app_search::toggle_running());
+
+    let context = glib::MainContext::default();
+    let deadline = Instant::now() + Duration::from_secs(5);
+
+    while toggles.get() == 0 && Instant::now() < deadline {
+        context.iteration(false);
+    }
+
+    assert_eq!(toggles.get(), 1, "the running launcher was not toggled");
+}
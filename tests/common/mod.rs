@@ -0,0 +1,31 @@
+use std::{
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+};
+
+pub struct Bus(Child);
+
+impl Drop for Bus {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+// A private session bus, so the tests never talk to the running desktop.
+pub fn spawn_bus() -> Option<(Bus, String)> {
+    let mut bus = Bus(Command::new("dbus-daemon")
+        .args(["--session", "--nofork", "--print-address"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?);
+
+    let mut address = String::new();
+
+    BufReader::new(bus.0.stdout.take()?)
+        .read_line(&mut address)
+        .ok()?;
+
+    Some((bus, address.trim().into()))
+}
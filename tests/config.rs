@@ -0,0 +1,97 @@
+use std::{fs, path::PathBuf, process};
+
+use barry::config::Config;
+
+// Loads `toml` as a config file and returns the error it is rejected with.
+fn error(name: &str, toml: &str) -> (PathBuf, String) {
+    let path = std::env::temp_dir().join(format!("barry-{}-{name}.toml", process::id()));
+
+    fs::write(&path, toml).unwrap();
+
+    let result = Config::load_from(&path);
+
+    fs::remove_file(&path).unwrap();
+
+    let error = result.expect_err("the config should be rejected");
+
+    (path, error.to_string())
+}
+
+#[test]
+fn reports_unknown_modules() {
+    let (path, error) = error("unknown", "[[bar.end]]\ntype = \"clock\"\n");
+
+    assert_eq!(
+        error,
+        format!(
+            "invalid config {}: unknown module `clock`, expected one of `current-track`, `date-time`, `metrics`, `volume`, `workspaces`",
+            path.display()
+        )
+    );
+}
+
+#[test]
+fn reports_unknown_options() {
+    let (path, error) = error(
+        "unknown-option",
+        "[[bar.end]]\ntype = \"date-time\"\ncolour = \"red\"\n",
+    );
+
+    assert_eq!(
+        error,
+        format!(
+            "invalid config {}: invalid options for module `date-time`: unknown field `colour`, expected one of `time-format`, `date-format`, `interval`",
+            path.display()
+        )
+    );
+}
+
+#[test]
+fn reports_mistyped_options() {
+    let (path, error) = error(
+        "mistyped-option",
+        "[[bar.end]]\ntype = \"metrics\"\ninterval = \"soon\"\n",
+    );
+
+    assert_eq!(
+        error,
+        format!(
+            "invalid config {}: invalid options for module `metrics`: invalid type: string \"soon\", expected a nonzero u64",
+            path.display()
+        )
+    );
+}
+
+#[test]
+fn reports_zero_intervals() {
+    let (path, error) = error(
+        "zero-interval",
+        "[[bar.end]]\ntype = \"date-time\"\ninterval = 0\n",
+    );
+
+    assert_eq!(
+        error,
+        format!(
+            "invalid config {}: invalid options for module `date-time`: invalid value: integer `0`, expected a nonzero u64",
+            path.display()
+        )
+    );
+}
+
+#[test]
+fn reports_invalid_exclusive_zones() {
+    let (path, error) = error("exclusive-zone", "[bar]\nexclusive-zone = \"big\"\n");
+
+    assert_eq!(
+        error,
+        format!(
+            "invalid config {}: TOML parse error at line 2, column 18
+  |
+2 | exclusive-zone = \"big\"
+  |                  ^^^^^
+invalid exclusive zone `big`, expected `auto` or a size in pixels
+",
+            path.display()
+        )
+    );
+}
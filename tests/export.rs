@@ -0,0 +1,69 @@
+use barry::theme::{
+    export::{render, TemplateError},
+    theme,
+};
+use material_colors::color::Argb;
+
+fn source() -> Argb {
+    Argb::from_u32(0x802C563E)
+}
+
+#[test]
+fn renders_every_format() {
+    let rendered = render(
+        "{{source}} {{ source.hex_stripped }} {{source.rgba_hex}}\n{{source.rgb}} {{source.rgba}}",
+        &theme(),
+        source(),
+    );
+
+    assert_eq!(
+        rendered.as_deref(),
+        Ok("#2c563e 2c563e 2c563e80\nrgb(44, 86, 62) rgba(44, 86, 62, 0.5)")
+    );
+}
+
+#[test]
+fn renders_roles() {
+    let scheme = theme();
+    let rendered = render("border = {{primary}};", &scheme, source());
+
+    assert_eq!(
+        rendered,
+        Ok(format!(
+            "border = #{:02x}{:02x}{:02x};",
+            scheme.primary.red, scheme.primary.green, scheme.primary.blue
+        ))
+    );
+}
+
+#[test]
+fn leaves_text_without_placeholders() {
+    assert_eq!(
+        render("a { b }\n}}", &theme(), source()).as_deref(),
+        Ok("a { b }\n}}")
+    );
+}
+
+#[test]
+fn reports_unknown_roles() {
+    assert_eq!(
+        render("a\nb {{primry}}", &theme(), source()),
+        Err(TemplateError::UnknownRole(2, "primry".into()))
+    );
+}
+
+#[test]
+fn reports_unknown_formats() {
+    assert_eq!(
+        render("{{primary}}\n\n{{primary.hsl}}", &theme(), source()),
+        Err(TemplateError::UnknownFormat(3, "hsl".into()))
+    );
+}
+
+#[test]
+fn reports_unclosed_placeholders() {
+    let error = render("{{primary}}\n{{primary", &theme(), source()).unwrap_err();
+
+    assert_eq!(error, TemplateError::Unclosed(2));
+    assert_eq!(error.to_string(), "line 2: placeholder is missing its `}}`");
+}
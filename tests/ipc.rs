@@ -0,0 +1,63 @@
+use barry::{
+    config::Color,
+    ipc::{Request, COMMANDS},
+};
+use material_colors::color::Argb;
+
+#[test]
+fn requests_round_trip() {
+    let requests = [
+        Request::ToggleAppSearch,
+        Request::Reload,
+        Request::Theme(Color(Argb::from_u32(0xFF2C563E))),
+        Request::Show,
+        Request::Hide,
+        Request::Toggle,
+        Request::Modules,
+    ];
+
+    for request in requests {
+        assert_eq!(request.to_string().parse(), Ok(request));
+    }
+}
+
+#[test]
+fn parses_surrounding_whitespace() {
+    assert_eq!(
+        "  theme   #2C563E \n".parse(),
+        Ok(Request::Theme(Color(Argb::from_u32(0xFF2C563E))))
+    );
+    assert_eq!("reload\n".parse(), Ok(Request::Reload));
+}
+
+#[test]
+fn reports_missing_commands() {
+    assert_eq!(
+        " \n".parse::<Request>(),
+        Err(format!("missing command, expected one of {COMMANDS}"))
+    );
+}
+
+#[test]
+fn reports_invalid_commands() {
+    assert_eq!(
+        "reload now\n".parse::<Request>(),
+        Err(format!(
+            "invalid command `reload now`, expected one of {COMMANDS}"
+        ))
+    );
+    assert_eq!(
+        "theme".parse::<Request>(),
+        Err(format!(
+            "invalid command `theme`, expected one of {COMMANDS}"
+        ))
+    );
+}
+
+#[test]
+fn reports_invalid_colors() {
+    assert_eq!(
+        "theme green".parse::<Request>(),
+        Err("invalid color `green`, expected a hex color like `#2C563E`".into())
+    );
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use std::time::Duration;
+
+use barry::theme::{portal, Appearance, ColorScheme};
+use material_colors::color::Argb;
+use tokio::{sync::mpsc, time::timeout};
+use zbus::{
+    connection, fdo, interface,
+    zvariant::{self, OwnedValue, Value},
+};
+
+const PATH: &str = "/org/freedesktop/portal/desktop";
+const INTERFACE: &str = "org.freedesktop.portal.Settings";
+
+struct MockSettings {
+    color_scheme: u32,
+    accent: (f64, f64, f64),
+}
+
+#[interface(name = "org.freedesktop.portal.Settings")]
+impl MockSettings {
+    fn read_one(&self, namespace: &str, key: &str) -> fdo::Result<OwnedValue> {
+        let value = match (namespace, key) {
+            (portal::NAMESPACE, portal::COLOR_SCHEME) => Value::from(self.color_scheme),
+            (portal::NAMESPACE, portal::ACCENT_COLOR) => Value::from(self.accent),
+            _ => {
+                return Err(fdo::Error::Failed(format!(
+                    "unknown setting {namespace} {key}"
+                )))
+            }
+        };
+
+        value
+            .try_into()
+            .map_err(|error: zvariant::Error| fdo::Error::Failed(error.to_string()))
+    }
+}
+
+async fn next(receiver: &mut mpsc::UnboundedReceiver<Appearance>) -> Appearance {
+    timeout(Duration::from_secs(5), receiver.recv())
+        .await
+        .expect("the portal watcher did not report a change")
+        .expect("the portal watcher stopped")
+}
+
+#[tokio::test]
+async fn follows_the_portal_appearance() {
+    let (_bus, address) = common::spawn_bus().expect("dbus-daemon is needed to run this test");
+
+    let portal = connection::Builder::address(address.as_str())
+        .unwrap()
+        .name("org.freedesktop.portal.Desktop")
+        .unwrap()
+        .serve_at(
+            PATH,
+            MockSettings {
+                color_scheme: 1,
+                accent: (1.0, 0.5, 0.0),
+            },
+        )
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+
+    let client = connection::Builder::address(address.as_str())
+        .unwrap()
+        .build()
+        .await
+        .unwrap();
+
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+
+    tokio::spawn(async move {
+        portal::watch(&client, move |appearance| {
+            let _ = sender.send(appearance);
+        })
+        .await
+    });
+
+    assert_eq!(
+        next(&mut receiver).await,
+        Appearance {
+            color_scheme: ColorScheme::Dark,
+            accent: Some(Argb::from_u32(0xFFFF8000)),
+        }
+    );
+
+    portal
+        .emit_signal(
+            None::<&str>,
+            PATH,
+            INTERFACE,
+            "SettingChanged",
+            &(portal::NAMESPACE, portal::COLOR_SCHEME, Value::from(2u32)),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(next(&mut receiver).await.color_scheme, ColorScheme::Light);
+
+    portal
+        .emit_signal(
+            None::<&str>,
+            PATH,
+            INTERFACE,
+            "SettingChanged",
+            &(
+                portal::NAMESPACE,
+                portal::ACCENT_COLOR,
+                Value::from((-1.0, -1.0, -1.0)),
+            ),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        next(&mut receiver).await,
+        Appearance {
+            color_scheme: ColorScheme::Light,
+            accent: None,
+        }
+    );
+}
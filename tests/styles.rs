@@ -0,0 +1,574 @@
+use std::time::Duration;
+
+use barry::styling::{
+    background::{BlendMode, Direction, Gradient, Image, RadialShape, Stop},
+    border::{Border, BorderRadius, BorderStyle},
+    font::FontWeight,
+    length::{px, Length},
+    parse_errors,
+    property::{Property, Value},
+    selector::{Compound, Node, PseudoClass, Selector},
+    style::Style,
+    stylesheet::StyleSheet,
+    thickness::Thickness,
+    token::{Elevation, Shape, Typescale},
+    transition::{Easing, Transition},
+};
+use material_colors::color::Argb;
+
+fn colors() -> (Argb, Argb) {
+    (Argb::from_u32(0xFFFF0000), Argb::from_u32(0x800000FF))
+}
+
+fn styles() -> Vec<(&'static str, Style)> {
+    let (red, blue) = colors();
+
+    let mut styles = vec![
+        (
+            "background image",
+            Style::new().background_image("/tmp/art.png"),
+        ),
+        ("background size", Style::new().background_size("cover")),
+        (
+            "background repeat",
+            Style::new().background_repeat("no-repeat"),
+        ),
+        (
+            "background position",
+            Style::new().background_position("center"),
+        ),
+        ("background color", Style::new().background_color(red)),
+        (
+            "linear gradient",
+            Style::new().background_gradient(
+                Gradient::linear(
+                    Direction::Angle(-45.5),
+                    red,
+                    Stop::at(blue, Length::Percent(75.0)),
+                )
+                .stop(red),
+            ),
+        ),
+        (
+            "radial gradient",
+            Style::new().background_gradient(Gradient::radial(
+                RadialShape::Circle,
+                Stop::at(red, px(0)),
+                blue,
+            )),
+        ),
+        (
+            "background layers",
+            Style::new().background_layers(&[
+                Gradient::linear(Direction::ToRight, red, blue).into(),
+                Image::File("/tmp/it's \"art\" in a \\ folder.png".into()),
+            ]),
+        ),
+        (
+            "background blend modes",
+            Style::new().background_blend_mode(&[BlendMode::Multiply, BlendMode::SoftLight]),
+        ),
+        ("color", Style::new().color(blue)),
+        (
+            "border radius",
+            Style::new().border_radius(&BorderRadius::All(px(4))),
+        ),
+        (
+            "border radius corners",
+            Style::new()
+                .border_radius(&BorderRadius::Custom(
+                    px(1),
+                    Length::Em(0.5),
+                    Length::Rem(1.0),
+                    Length::Percent(50.0),
+                ))
+                .border_radius(&BorderRadius::TopLeft(px(2)))
+                .border_radius(&BorderRadius::BottomRight(px(-2))),
+        ),
+        ("font size", Style::new().font_size(12)),
+        (
+            "relative font size",
+            Style::new().font_size(Length::Em(1.25)),
+        ),
+        ("min width", Style::new().min_width(19)),
+        ("min height", Style::new().min_height(Length::Rem(2.0))),
+        ("min size", Style::new().min_size(24)),
+        (
+            "margin",
+            Style::new().margin(&Thickness::Custom(px(-4), Length::Em(0.5), px(0), px(2))),
+        ),
+        ("padding", Style::new().padding(&Thickness::All(px(4)))),
+        (
+            "padding side",
+            Style::new().padding(&Thickness::Left(px(-4))),
+        ),
+        (
+            "border",
+            Style::new().border(
+                &Border::default()
+                    .thickness(1)
+                    .style(BorderStyle::Solid)
+                    .color(red),
+            ),
+        ),
+        ("box shadow", Style::new().box_shadow([0, 0, 4], blue)),
+        (
+            "percent box shadow",
+            Style::new().box_shadow([px(0), px(0), Length::Percent(10.0)], blue),
+        ),
+        (
+            "percent min size",
+            Style::new()
+                .min_width(Length::Percent(50.0))
+                .min_height(Length::Percent(50.0))
+                .min_size(Length::Percent(25.0)),
+        ),
+        (
+            "percent margin",
+            Style::new().margin(&Thickness::Custom(
+                Length::Percent(10.0),
+                px(2),
+                Length::Percent(-5.0),
+                px(2),
+            )),
+        ),
+        (
+            "percent padding",
+            Style::new().padding(&Thickness::All(Length::Percent(10.0))),
+        ),
+        (
+            "percent border",
+            Style::new().border(
+                &Border::default()
+                    .thickness(Length::Percent(10.0))
+                    .style(BorderStyle::Solid)
+                    .color(red),
+            ),
+        ),
+        (
+            "box shadow lengths",
+            Style::new().box_shadow([px(1), Length::Em(-0.5), px(-4), px(-1)], blue),
+        ),
+        (
+            "relative border",
+            Style::new().border(
+                &Border::default()
+                    .thickness(Length::Em(0.1))
+                    .style(BorderStyle::Dashed)
+                    .color(red),
+            ),
+        ),
+        (
+            "font family",
+            Style::new().font_family("JetBrainsMono Nerd Font"),
+        ),
+        ("thin font", Style::new().font_weight(&FontWeight::Thin)),
+        ("normal font", Style::new().font_weight(&FontWeight::Normal)),
+        ("medium font", Style::new().font_weight(&FontWeight::Medium)),
+        ("bold font", Style::new().font_weight(&FontWeight::Bold)),
+        (
+            "transitions",
+            Style::new().transition(&[
+                Transition::default().duration(Duration::from_millis(300)),
+                Transition::new("color")
+                    .duration(Duration::from_secs(1))
+                    .easing(Easing::CubicBezier(0.2, 0.0, 0.0, 1.0))
+                    .delay(Duration::from_millis(50)),
+            ]),
+        ),
+    ];
+
+    styles.extend(
+        [
+            BorderStyle::None,
+            BorderStyle::Solid,
+            BorderStyle::Inset,
+            BorderStyle::Outset,
+            BorderStyle::Hidden,
+            BorderStyle::Dotted,
+            BorderStyle::Dashed,
+            BorderStyle::Double,
+            BorderStyle::Groove,
+            BorderStyle::Ridge,
+        ]
+        .map(|style| {
+            (
+                "border style",
+                Style::new().border(&Border::default().thickness(2).style(style).color(red)),
+            )
+        }),
+    );
+
+    styles.extend(
+        [
+            Easing::Linear,
+            Easing::Ease,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::StepStart,
+            Easing::StepEnd,
+            Easing::CubicBezier(0.2, 0.0, 0.0, 1.0),
+            Easing::CubicBezier(1.5, -0.5, -0.5, 1.5),
+        ]
+        .map(|easing| {
+            (
+                "easing",
+                Style::new().transition(&[Transition::new("opacity")
+                    .duration(Duration::from_millis(200))
+                    .easing(easing)]),
+            )
+        }),
+    );
+
+    styles.extend(
+        [
+            Direction::Angle(90.0),
+            Direction::ToTop,
+            Direction::ToRight,
+            Direction::ToBottom,
+            Direction::ToLeft,
+        ]
+        .map(|direction| {
+            (
+                "gradient direction",
+                Style::new().background_gradient(Gradient::linear(direction, red, blue)),
+            )
+        }),
+    );
+
+    styles.extend([RadialShape::Circle, RadialShape::Ellipse].map(|shape| {
+        (
+            "radial shape",
+            Style::new().background_gradient(Gradient::radial(shape, red, blue)),
+        )
+    }));
+
+    styles.extend(
+        [
+            BlendMode::Normal,
+            BlendMode::Multiply,
+            BlendMode::Screen,
+            BlendMode::Overlay,
+            BlendMode::Darken,
+            BlendMode::Lighten,
+            BlendMode::ColorDodge,
+            BlendMode::ColorBurn,
+            BlendMode::HardLight,
+            BlendMode::SoftLight,
+            BlendMode::Difference,
+            BlendMode::Exclusion,
+            BlendMode::Color,
+            BlendMode::Hue,
+            BlendMode::Saturation,
+            BlendMode::Luminosity,
+        ]
+        .map(|mode| ("blend mode", Style::new().background_blend_mode(&[mode]))),
+    );
+
+    styles.extend(
+        [
+            Typescale::DisplayLarge,
+            Typescale::DisplayMedium,
+            Typescale::DisplaySmall,
+            Typescale::HeadlineLarge,
+            Typescale::HeadlineMedium,
+            Typescale::HeadlineSmall,
+            Typescale::TitleLarge,
+            Typescale::TitleMedium,
+            Typescale::TitleSmall,
+            Typescale::BodyLarge,
+            Typescale::BodyMedium,
+            Typescale::BodySmall,
+            Typescale::LabelLarge,
+            Typescale::LabelMedium,
+            Typescale::LabelSmall,
+        ]
+        .map(|role| ("typescale", Style::new().typescale(role))),
+    );
+
+    styles.extend(
+        [
+            Shape::None,
+            Shape::ExtraSmall,
+            Shape::Small,
+            Shape::Medium,
+            Shape::Large,
+            Shape::ExtraLarge,
+            Shape::Full,
+        ]
+        .map(|shape| ("shape", Style::new().shape(shape))),
+    );
+
+    styles.extend(
+        [
+            Elevation::Level0,
+            Elevation::Level1,
+            Elevation::Level2,
+            Elevation::Level3,
+            Elevation::Level4,
+            Elevation::Level5,
+        ]
+        .map(|elevation| ("elevation", Style::new().elevation(elevation, blue))),
+    );
+
+    styles
+}
+
+fn stylesheet() -> StyleSheet {
+    let (red, blue) = colors();
+    let pseudo_classes = [
+        PseudoClass::Hover,
+        PseudoClass::Active,
+        PseudoClass::Focus,
+        PseudoClass::FocusWithin,
+        PseudoClass::FocusVisible,
+        PseudoClass::Checked,
+        PseudoClass::Selected,
+        PseudoClass::Disabled,
+        PseudoClass::Backdrop,
+        PseudoClass::FirstChild,
+        PseudoClass::LastChild,
+        PseudoClass::OnlyChild,
+        PseudoClass::NthChild(2, 1),
+        PseudoClass::NthChild(0, 3),
+        PseudoClass::NthLastChild(3, -1),
+    ];
+    let nodes = [
+        Node::Any,
+        Node::Box,
+        Node::Button,
+        Node::Label,
+        Node::Image,
+        Node::Entry,
+        Node::Text,
+        Node::Scale,
+        Node::Trough,
+        Node::Highlight,
+        Node::Slider,
+        Node::Progress,
+        Node::ProgressBar,
+        Node::Row,
+        Node::ListView,
+        Node::Popover,
+        Node::Contents,
+        Node::Arrow,
+        Node::ScrolledWindow,
+        Node::Scrollbar,
+        Node::Undershoot,
+    ];
+
+    let stylesheet = StyleSheet::new()
+        .default_style(Style::new().color(red))
+        .style_for(
+            Selector::new().class("active").hover().active().focus(),
+            Style::new().color(blue),
+        )
+        .style_for(
+            Selector::new()
+                .focus_within()
+                .checked()
+                .disabled()
+                .first_child()
+                .last_child()
+                .nth_child(2, 0),
+            Style::new().color(blue),
+        )
+        .style_for(
+            Selector::new().child(Node::Trough).child(Node::Highlight),
+            Style::new().color(blue),
+        )
+        .style_for(
+            Selector::new()
+                .descendant(Node::Label)
+                .next_sibling(Node::Image)
+                .sibling(Node::Entry),
+            Style::new().color(blue),
+        )
+        .style_for(
+            Selector::new().not(Compound::new().class("exists")),
+            Style::new().color(blue),
+        )
+        .style_for(
+            Selector::new().not(Compound::new()),
+            Style::new().color(blue),
+        )
+        .style_for(
+            Selector::new()
+                .child(Node::Button)
+                .not(Node::Label.into())
+                .not(
+                    Compound::new()
+                        .node(Node::Button)
+                        .class("flat")
+                        .pseudo(PseudoClass::Hover),
+                ),
+            Style::new().color(blue),
+        );
+
+    let stylesheet = pseudo_classes
+        .into_iter()
+        .fold(stylesheet, |stylesheet, pseudo_class| {
+            stylesheet.style_for(pseudo_class, Style::new().color(blue))
+        });
+
+    nodes.into_iter().fold(stylesheet, |stylesheet, node| {
+        stylesheet.style_for(
+            Selector::new().child(node).class("inner").hover(),
+            Style::new().color(blue),
+        )
+    })
+}
+
+// GTK can only be used from one thread, so everything is parsed in one test.
+#[test]
+fn generated_css_parses() {
+    gtk::init().expect("a display is needed to run this test");
+
+    assert!(
+        !parse_errors("box { colour: red; }").is_empty(),
+        "parse errors are not reported"
+    );
+
+    for (name, style) in styles() {
+        let css = style.with_class_name("box");
+
+        assert_eq!(parse_errors(&css), vec![], "{name}: {css}");
+    }
+
+    let css = stylesheet().with_class_name("scale");
+
+    assert_eq!(parse_errors(&css), vec![], "{css}");
+}
+
+#[test]
+fn image_paths_are_escaped() {
+    let image = Image::File("/tmp/it's \"art\" in a \\ folder.png".into());
+
+    assert_eq!(
+        image.to_string(),
+        r#"url("file:///tmp/it's \"art\" in a \\ folder.png")"#
+    );
+}
+
+#[test]
+fn transition_properties_are_identifiers() {
+    assert!("300 ease".parse::<Transition>().is_err());
+    assert!("-5 1s".parse::<Transition>().is_err());
+    assert_eq!(
+        "-gtk-icon-size 1s".parse::<Transition>(),
+        Ok(Transition::new("-gtk-icon-size").duration(Duration::from_secs(1)))
+    );
+}
+
+#[test]
+fn percentages_are_left_out_where_gtk_rejects_them() {
+    let (red, _) = colors();
+    let style = Style::new()
+        .min_size(Length::Percent(50.0))
+        .margin(&Thickness::Custom(
+            Length::Percent(10.0),
+            px(2),
+            px(2),
+            px(2),
+        ))
+        .padding(&Thickness::Left(Length::Percent(10.0)))
+        .box_shadow([px(0), Length::Percent(10.0)], red)
+        .border(
+            &Border::default()
+                .thickness(2)
+                .thickness(Length::Percent(10.0)),
+        );
+
+    for property in [
+        Property::MinWidth,
+        Property::MinHeight,
+        Property::MarginTop,
+        Property::PaddingLeft,
+        Property::BoxShadow,
+    ] {
+        assert_eq!(style.get(property), None, "{property}");
+    }
+
+    assert_eq!(
+        style.get(Property::MarginRight),
+        Some(&Value::Length(px(2)))
+    );
+    assert_eq!(
+        style.get(Property::Border),
+        Some(&Value::Border(Border::default().thickness(2)))
+    );
+}
+
+#[test]
+fn later_setters_override_earlier_ones() {
+    let style = Style::new()
+        .border_radius(&BorderRadius::All(px(4)))
+        .border_radius(&BorderRadius::TopLeft(px(8)));
+
+    assert_eq!(
+        style.get(Property::BorderTopLeftRadius),
+        Some(&Value::Length(px(8)))
+    );
+
+    for property in [
+        Property::BorderTopRightRadius,
+        Property::BorderBottomRightRadius,
+        Property::BorderBottomLeftRadius,
+    ] {
+        assert_eq!(
+            style.get(property),
+            Some(&Value::Length(px(4))),
+            "{property}"
+        );
+    }
+}
+
+#[test]
+fn without_removes_only_that_property() {
+    let (red, blue) = colors();
+    let style = Style::new()
+        .color(red)
+        .background_color(blue)
+        .without(Property::Color);
+
+    assert_eq!(style.get(Property::Color), None);
+    assert_eq!(
+        style.properties().collect::<Vec<_>>(),
+        vec![(Property::BackgroundColor, &Value::Color(blue))]
+    );
+}
+
+#[test]
+fn diff_lists_removed_and_changed_properties() {
+    let (red, blue) = colors();
+    let before = Style::new().color(red).background_color(red).min_width(4);
+    let after = Style::new().color(red).background_color(blue).font_size(12);
+
+    assert_eq!(
+        before.diff(&after),
+        vec![
+            (Property::BackgroundColor, Some(&Value::Color(blue))),
+            (Property::FontSize, Some(&Value::Length(px(12)))),
+            (Property::MinWidth, None),
+        ]
+    );
+    assert_eq!(after.diff(&after), vec![]);
+}
+
+#[test]
+fn merged_styles_win_on_conflicts() {
+    let (red, blue) = colors();
+    let base = Style::new().color(red).min_width(4);
+    let other = Style::new().color(blue).font_size(12);
+
+    let mut extended = base.clone();
+
+    extended.extend(other.clone());
+
+    for merged in [base.merge(&other), extended] {
+        assert_eq!(merged.get(Property::Color), Some(&Value::Color(blue)));
+        assert_eq!(merged.get(Property::MinWidth), Some(&Value::Length(px(4))));
+        assert_eq!(merged.get(Property::FontSize), Some(&Value::Length(px(12))));
+    }
+}